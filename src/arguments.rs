@@ -0,0 +1,96 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Looks up a single anime or manga. The media type is set by the getter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MediaArguments {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id_mal: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_adult: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    season_year: Option<i64>,
+}
+
+impl MediaArguments {
+    pub fn id(id: i64) -> Self {
+        Self {
+            id: Some(id),
+            ..Default::default()
+        }
+    }
+
+    pub fn mal_id(id_mal: i64) -> Self {
+        Self {
+            id_mal: Some(id_mal),
+            ..Default::default()
+        }
+    }
+
+    pub fn search(search: impl Into<String>) -> Self {
+        Self {
+            search: Some(search.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_is_adult(mut self, is_adult: bool) -> Self {
+        self.is_adult = Some(is_adult);
+
+        self
+    }
+
+    pub fn with_season_year(mut self, season_year: i64) -> Self {
+        self.season_year = Some(season_year);
+
+        self
+    }
+
+    pub(crate) fn to_variables(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+impl From<i64> for MediaArguments {
+    fn from(id: i64) -> Self {
+        MediaArguments::id(id)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CharacterArguments {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    search: Option<String>,
+}
+
+impl CharacterArguments {
+    pub fn id(id: i64) -> Self {
+        Self {
+            id: Some(id),
+            ..Default::default()
+        }
+    }
+
+    pub fn search(search: impl Into<String>) -> Self {
+        Self {
+            search: Some(search.into()),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn to_variables(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+impl From<i64> for CharacterArguments {
+    fn from(id: i64) -> Self {
+        CharacterArguments::id(id)
+    }
+}
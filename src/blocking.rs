@@ -0,0 +1,249 @@
+use crate::arguments::{CharacterArguments, MediaArguments};
+use crate::cache::ResponseCache;
+use crate::entity_store::EntityStore;
+use crate::errors::Error;
+use crate::models::{Anime, Character, Manga, Status};
+use crate::oauth::{AccessToken, ClientCredentials};
+use crate::rate_limit::{Priority, RateLimit};
+use crate::response::Response;
+use crate::session::FavouriteTarget;
+use crate::token_store::{StoredToken, TokenStore};
+use crate::AniListClientBuilder;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// The blocking counterpart of [`crate::AniListClient`], built from the same
+/// [`AniListClientBuilder`] with [`AniListClientBuilder::build_blocking`]. Requests run on a
+/// single-threaded runtime owned by the client, so its methods must not be called from within an
+/// async context. Background work, such as refreshing the stale entries served with
+/// [`crate::CachePolicy::with_stale_while_revalidate`], runs on that runtime too and only makes
+/// progress during later calls of the client.
+#[derive(Clone)]
+pub struct AniListClient {
+    client: crate::AniListClient,
+    runtime: Arc<Runtime>,
+}
+
+/// The blocking counterpart of [`crate::Session`].
+#[derive(Clone, Debug)]
+pub struct Session {
+    session: crate::Session,
+    runtime: Arc<Runtime>,
+}
+
+impl AniListClientBuilder {
+    pub fn build_blocking(&self) -> Result<AniListClient, Error> {
+        AniListClient::new(self.build()?)
+    }
+}
+
+impl AniListClient {
+    pub fn new(client: crate::AniListClient) -> Result<Self, Error> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        Ok(Self {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client sharing the transport, rate limit and cache of this one.
+    pub fn as_async(&self) -> &crate::AniListClient {
+        &self.client
+    }
+
+    /// Runs any future of the async API, e.g. the invalidation methods of [`ResponseCache`].
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub fn with_priority(&self, priority: Priority) -> Self {
+        Self {
+            client: self.client.with_priority(priority),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    pub fn priority(&self) -> Priority {
+        self.client.priority()
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.client.rate_limit()
+    }
+
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.client.cache()
+    }
+
+    pub fn entities(&self) -> Option<&EntityStore> {
+        self.client.entities()
+    }
+
+    pub fn get_anime(&self, arguments: impl Into<MediaArguments>) -> Result<Anime, Error> {
+        self.block_on(self.client.get_anime(arguments))
+    }
+
+    pub fn get_anime_with_variables(&self, variables: Value) -> Result<Anime, Error> {
+        self.block_on(self.client.get_anime_with_variables(variables))
+    }
+
+    pub fn get_manga(&self, arguments: impl Into<MediaArguments>) -> Result<Manga, Error> {
+        self.block_on(self.client.get_manga(arguments))
+    }
+
+    pub fn get_manga_with_variables(&self, variables: Value) -> Result<Manga, Error> {
+        self.block_on(self.client.get_manga_with_variables(variables))
+    }
+
+    pub fn get_character(
+        &self,
+        arguments: impl Into<CharacterArguments>,
+    ) -> Result<Character, Error> {
+        self.block_on(self.client.get_character(arguments))
+    }
+
+    pub fn get_character_with_variables(&self, variables: Value) -> Result<Character, Error> {
+        self.block_on(self.client.get_character_with_variables(variables))
+    }
+
+    pub fn execute<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T, Error> {
+        self.block_on(self.client.execute(query, variables))
+    }
+
+    pub fn execute_partial<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<Response<T>, Error> {
+        self.block_on(self.client.execute_partial(query, variables))
+    }
+
+    pub fn exchange_code(
+        &self,
+        credentials: &ClientCredentials,
+        code: &str,
+    ) -> Result<AccessToken, Error> {
+        self.block_on(self.client.exchange_code(credentials, code))
+    }
+
+    pub fn session(&self, access_token: impl Into<String>) -> Session {
+        self.wrap_session(self.client.session(access_token))
+    }
+
+    pub fn session_from_store(&self, store: impl TokenStore + 'static, user_id: i64) -> Session {
+        self.wrap_session(self.client.session_from_store(store, user_id))
+    }
+
+    pub fn viewer_session(&self) -> Result<Session, Error> {
+        self.client
+            .viewer_session()
+            .map(|session| self.wrap_session(session))
+    }
+
+    fn wrap_session(&self, session: crate::Session) -> Session {
+        Session {
+            session,
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+}
+
+impl Session {
+    pub fn as_async(&self) -> &crate::Session {
+        &self.session
+    }
+
+    pub fn with_expiry_warning(
+        self,
+        before: Duration,
+        callback: impl Fn(&StoredToken) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            session: self.session.with_expiry_warning(before, callback),
+            runtime: self.runtime,
+        }
+    }
+
+    pub fn with_priority(self, priority: Priority) -> Self {
+        Self {
+            session: self.session.with_priority(priority),
+            runtime: self.runtime,
+        }
+    }
+
+    pub fn access_token(&self) -> Result<Arc<str>, Error> {
+        self.runtime.block_on(self.session.access_token())
+    }
+
+    pub fn viewer(&self) -> Result<Value, Error> {
+        self.runtime.block_on(self.session.viewer())
+    }
+
+    pub fn notifications(
+        &self,
+        page: i64,
+        per_page: i64,
+        reset_count: bool,
+    ) -> Result<Value, Error> {
+        self.runtime
+            .block_on(self.session.notifications(page, per_page, reset_count))
+    }
+
+    pub fn set_progress(&self, new_chapter: i64, media_id: i64) -> Result<Value, Error> {
+        self.runtime
+            .block_on(self.session.set_progress(new_chapter, media_id))
+    }
+
+    pub fn set_increment_progress(
+        &self,
+        progress_start: i64,
+        progress_end: i64,
+        media_id: i64,
+    ) -> Result<Value, Error> {
+        self.runtime.block_on(self.session.set_increment_progress(
+            progress_start,
+            progress_end,
+            media_id,
+        ))
+    }
+
+    pub fn toggle_favourite(&self, target: FavouriteTarget) -> Result<Value, Error> {
+        self.runtime.block_on(self.session.toggle_favourite(target))
+    }
+
+    pub fn save_list_entry(
+        &self,
+        media_id: i64,
+        status: Option<Status>,
+        score: Option<f64>,
+    ) -> Result<Value, Error> {
+        self.runtime
+            .block_on(self.session.save_list_entry(media_id, status, score))
+    }
+
+    pub fn delete_list_entry(&self, entry_id: i64) -> Result<Value, Error> {
+        self.runtime
+            .block_on(self.session.delete_list_entry(entry_id))
+    }
+
+    pub fn execute<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T, Error> {
+        self.runtime
+            .block_on(self.session.execute(query, variables))
+    }
+
+    pub fn execute_partial<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<Response<T>, Error> {
+        self.runtime
+            .block_on(self.session.execute_partial(query, variables))
+    }
+}
@@ -0,0 +1,109 @@
+use crate::cache::CacheEntry;
+use crate::entity_store::EntityKind;
+use crate::errors::Error;
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// One JSON file per request, named after the hash of its cache key, see `ResponseCache::key`.
+#[derive(Debug)]
+pub(crate) struct DiskCache {
+    directory: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    #[serde(flatten)]
+    entry: CacheEntry,
+}
+
+impl DiskCache {
+    pub(crate) fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Unreadable or corrupted files are treated as missing entries.
+    pub(crate) async fn get(&self, key: &str) -> Option<CacheEntry> {
+        let content = tokio::fs::read(self.path(key)).await.ok()?;
+        let disk_entry: DiskEntry = serde_json::from_slice(&content).ok()?;
+
+        (disk_entry.key == key).then_some(disk_entry.entry)
+    }
+
+    /// Writes to a temporary file first, so that a crash never leaves a truncated entry.
+    pub(crate) async fn insert(&self, key: &str, entry: &CacheEntry) -> Result<(), Error> {
+        let content = serde_json::to_vec(&DiskEntry {
+            key: key.to_owned(),
+            entry: entry.clone(),
+        })
+        .map_err(|error| Error::decode("", error))?;
+        let path = self.path(key);
+        let temporary_path = path.with_extension("json.tmp");
+
+        tokio::fs::create_dir_all(&self.directory).await?;
+        tokio::fs::write(&temporary_path, content).await?;
+        tokio::fs::rename(&temporary_path, &path).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn remove(&self, key: &str) -> Result<(), Error> {
+        DiskCache::remove_file(self.path(key)).await
+    }
+
+    pub(crate) async fn remove_entity(&self, kind: EntityKind, id: i64) -> Result<(), Error> {
+        for path in self.files().await? {
+            let Ok(content) = tokio::fs::read(&path).await else {
+                continue;
+            };
+            match serde_json::from_slice::<DiskEntry>(&content) {
+                Ok(disk_entry) if !disk_entry.entry.contains(kind, id) => {}
+                _ => DiskCache::remove_file(path).await?,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn clear(&self) -> Result<(), Error> {
+        for path in self.files().await? {
+            DiskCache::remove_file(path).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn files(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut files: Vec<PathBuf> = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
+    }
+
+    async fn remove_file(path: PathBuf) -> Result<(), Error> {
+        match tokio::fs::remove_file(path).await {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory
+            .join(format!("{:016x}.json", crate::queries::hash(key)))
+    }
+}
@@ -0,0 +1,79 @@
+use crate::cache::CacheEntry;
+use crate::entity_store::EntityKind;
+use std::collections::{BTreeMap, HashMap};
+
+/// Least recently used entries are evicted first once `capacity` is reached. Expired entries are
+/// kept until then, so that they can still be served stale.
+#[derive(Debug)]
+pub(crate) struct MemoryCache {
+    capacity: usize,
+    entries: HashMap<String, (CacheEntry, u64)>,
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl MemoryCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn get(&mut self, key: &str) -> Option<CacheEntry> {
+        self.clock += 1;
+        let (entry, last_used) = self.entries.get_mut(key)?;
+        self.recency.remove(last_used);
+        self.recency.insert(self.clock, key.to_owned());
+        *last_used = self.clock;
+
+        Some(entry.clone())
+    }
+
+    pub(crate) fn insert(&mut self, key: String, entry: CacheEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.remove(&key);
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+
+        self.clock += 1;
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(key, (entry, self.clock));
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) {
+        if let Some((_, last_used)) = self.entries.remove(key) {
+            self.recency.remove(&last_used);
+        }
+    }
+
+    pub(crate) fn remove_entity(&mut self, kind: EntityKind, id: i64) {
+        let keys: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, (entry, _))| entry.contains(kind, id))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
@@ -0,0 +1,411 @@
+mod disk;
+mod memory;
+
+use crate::entity_store::{self, EntityKind};
+use crate::errors::Error;
+use crate::queries;
+use disk::DiskCache;
+use memory::MemoryCache;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+/// Fields whose value depends on the user of the token, responses selecting them are never
+/// cached for authenticated requests.
+const VIEWER_FIELDS: [&str; 10] = [
+    "Viewer",
+    "isFavourite",
+    "isFollowing",
+    "isFollower",
+    "isBlocked",
+    "isLiked",
+    "isSubscribed",
+    "mediaListEntry",
+    "notifications",
+    "unreadNotificationCount",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachePolicy {
+    max_entries: usize,
+    default_ttl: Duration,
+    finished_ttl: Duration,
+    airing_ttl: Duration,
+    operation_ttls: HashMap<String, Duration>,
+    disk_directory: Option<PathBuf>,
+    stale_while_revalidate: bool,
+    offline_fallback: bool,
+    max_stale: Duration,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: 256,
+            default_ttl: Duration::from_secs(30 * 60),
+            finished_ttl: Duration::from_secs(24 * 60 * 60),
+            airing_ttl: Duration::from_secs(5 * 60),
+            operation_ttls: HashMap::new(),
+            disk_directory: None,
+            stale_while_revalidate: false,
+            offline_fallback: false,
+            max_stale: Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+}
+
+impl CachePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+
+        self
+    }
+
+    pub fn with_default_ttl(mut self, default_ttl: Duration) -> Self {
+        self.default_ttl = default_ttl;
+
+        self
+    }
+
+    /// For media that are `FINISHED` or `CANCELLED`.
+    pub fn with_finished_ttl(mut self, finished_ttl: Duration) -> Self {
+        self.finished_ttl = finished_ttl;
+
+        self
+    }
+
+    /// For media that are `RELEASING`, `NOT_YET_RELEASED` or `HIATUS`, whose episodes, scores
+    /// and airing schedule change often.
+    pub fn with_airing_ttl(mut self, airing_ttl: Duration) -> Self {
+        self.airing_ttl = airing_ttl;
+
+        self
+    }
+
+    /// Overrides the TTL of a root field such as `Character` or `Page`, a zero TTL disables
+    /// caching for it.
+    pub fn with_operation_ttl(mut self, operation: impl Into<String>, ttl: Duration) -> Self {
+        self.operation_ttls.insert(operation.into(), ttl);
+
+        self
+    }
+
+    /// Also keeps the responses in `directory`, so that they survive restarts.
+    pub fn with_disk(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.disk_directory = Some(directory.into());
+
+        self
+    }
+
+    /// Answers with an expired entry right away and refreshes it in the background.
+    pub fn with_stale_while_revalidate(mut self, stale_while_revalidate: bool) -> Self {
+        self.stale_while_revalidate = stale_while_revalidate;
+
+        self
+    }
+
+    /// Answers with an expired entry instead of failing when the network is down or AniList
+    /// answers with a server error.
+    pub fn with_offline_fallback(mut self, offline_fallback: bool) -> Self {
+        self.offline_fallback = offline_fallback;
+
+        self
+    }
+
+    /// How long after their expiry entries may still be served stale, a week by default.
+    pub fn with_max_stale(mut self, max_stale: Duration) -> Self {
+        self.max_stale = max_stale;
+
+        self
+    }
+
+    /// The shortest TTL of the root fields of the query.
+    fn ttl(&self, query: &str, data: &Value) -> Duration {
+        queries::root_fields(query)
+            .into_iter()
+            .map(|(alias, field)| {
+                if let Some(ttl) = self.operation_ttls.get(&field) {
+                    return *ttl;
+                }
+
+                let value = &data[alias.as_str()];
+                let media: Vec<&Value> = match field.as_str() {
+                    "Media" => vec![value],
+                    "Page" => value["media"]
+                        .as_array()
+                        .map(|media| media.iter().collect())
+                        .unwrap_or_default(),
+                    _ => Vec::new(),
+                };
+                self.media_ttl(&media)
+            })
+            .min()
+            .unwrap_or(self.default_ttl)
+    }
+
+    fn media_ttl(&self, media: &[&Value]) -> Duration {
+        let statuses: Vec<&str> = media
+            .iter()
+            .filter_map(|media| media["status"].as_str())
+            .collect();
+
+        if statuses.is_empty() {
+            self.default_ttl
+        } else if statuses
+            .iter()
+            .all(|status| matches!(*status, "FINISHED" | "CANCELLED"))
+        {
+            self.finished_ttl
+        } else {
+            self.airing_ttl
+        }
+    }
+}
+
+/// Responses of queries, shared by all clones of a client. Mutations are never cached and evict
+/// the entries containing the media, character, staff or studio they target.
+#[derive(Debug)]
+pub struct ResponseCache {
+    policy: CachePolicy,
+    memory: Mutex<MemoryCache>,
+    disk: Option<DiskCache>,
+    revalidating: Mutex<HashSet<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CacheEntry {
+    pub(crate) data: Value,
+    stored_at: SystemTime,
+    expires_at: SystemTime,
+    entities: Vec<(EntityKind, i64)>,
+}
+
+impl CacheEntry {
+    pub(crate) fn is_fresh(&self) -> bool {
+        self.expires_at > SystemTime::now()
+    }
+
+    fn contains(&self, kind: EntityKind, id: i64) -> bool {
+        self.entities.contains(&(kind, id))
+    }
+}
+
+impl ResponseCache {
+    pub(crate) fn new(policy: CachePolicy) -> Self {
+        Self {
+            memory: Mutex::new(MemoryCache::new(policy.max_entries)),
+            disk: policy.disk_directory.clone().map(DiskCache::new),
+            revalidating: Mutex::new(HashSet::new()),
+            policy,
+        }
+    }
+
+    pub fn policy(&self) -> &CachePolicy {
+        &self.policy
+    }
+
+    /// The number of entries held in memory.
+    pub fn len(&self) -> usize {
+        self.memory().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub async fn clear(&self) -> Result<(), Error> {
+        self.memory().clear();
+        match &self.disk {
+            Some(disk) => disk.clear().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Evicts the response to the request sent with `access_token`, entries are kept per token.
+    pub async fn invalidate(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+    ) -> Result<(), Error> {
+        let key = ResponseCache::key(query, variables, access_token);
+        self.memory().remove(&key);
+        match &self.disk {
+            Some(disk) => disk.remove(&key).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Evicts every response containing the entity.
+    pub async fn invalidate_entity(&self, kind: EntityKind, id: i64) -> Result<(), Error> {
+        self.memory().remove_entity(kind, id);
+        match &self.disk {
+            Some(disk) => disk.remove_entity(kind, id).await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn invalidate_media(&self, media_id: i64) -> Result<(), Error> {
+        self.invalidate_entity(EntityKind::Media, media_id).await
+    }
+
+    /// Whether the request must skip the cache, `authenticated` is whether a token is sent.
+    pub(crate) fn bypasses(query: &str, authenticated: bool) -> bool {
+        queries::is_mutation(query)
+            || (authenticated
+                && query
+                    .split(|character: char| !character.is_alphanumeric() && character != '_')
+                    .any(|token| VIEWER_FIELDS.contains(&token)))
+    }
+
+    pub(crate) fn serves_stale_while_revalidating(&self) -> bool {
+        self.policy.stale_while_revalidate
+    }
+
+    /// Whether a stale entry may replace `error`.
+    pub(crate) fn serves_stale_on(&self, error: &Error) -> bool {
+        self.policy.offline_fallback
+            && match error {
+                Error::Transport(_) | Error::Timeout | Error::Unavailable { .. } => true,
+                error => error.status().is_some_and(|status| status >= 500),
+            }
+    }
+
+    /// The entry of the request, fresh or not older than the `max_stale` of the policy. Entries
+    /// found on disk are copied to memory.
+    pub(crate) async fn get(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+    ) -> Option<CacheEntry> {
+        let key = ResponseCache::key(query, variables, access_token);
+        let memory_entry = self.memory().get(&key);
+
+        let entry = match (memory_entry, &self.disk) {
+            (Some(memory_entry), _) if memory_entry.is_fresh() => memory_entry,
+            (memory_entry, Some(disk)) => match disk.get(&key).await {
+                Some(disk_entry)
+                    if memory_entry.as_ref().is_none_or(|memory_entry| {
+                        disk_entry.expires_at > memory_entry.expires_at
+                    }) =>
+                {
+                    self.memory().insert(key, disk_entry.clone());
+                    disk_entry
+                }
+                _ => memory_entry?,
+            },
+            (memory_entry, None) => memory_entry?,
+        };
+
+        let now = SystemTime::now();
+        entry
+            .expires_at
+            .checked_add(self.policy.max_stale)
+            .is_none_or(|limit| limit > now)
+            .then_some(entry)
+    }
+
+    /// Failing to write to disk does not fail the request, the entry is then only kept in memory.
+    pub(crate) async fn insert(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+        data: &Value,
+    ) {
+        let ttl = self.policy.ttl(query, data);
+        if ttl.is_zero() {
+            return;
+        }
+
+        let mut entities: Vec<(EntityKind, i64)> = Vec::new();
+        entity_store::visit_entities(data, &mut |kind, id, _| {
+            if !entities.contains(&(kind, id)) {
+                entities.push((kind, id));
+            }
+        });
+        let stored_at = SystemTime::now();
+        let entry = CacheEntry {
+            data: data.clone(),
+            stored_at,
+            expires_at: stored_at + ttl,
+            entities,
+        };
+        let key = ResponseCache::key(query, variables, access_token);
+        if let Some(disk) = &self.disk {
+            disk.insert(&key, &entry).await.ok();
+        }
+        self.memory().insert(key, entry);
+    }
+
+    /// Evicts what a successful mutation may have changed, based on its variables.
+    pub(crate) async fn invalidate_mutation(&self, variables: &Value) {
+        let Some(variables) = variables.as_object() else {
+            return;
+        };
+
+        for (name, value) in variables {
+            let kind = match name.as_str() {
+                "mediaId" | "animeId" | "mangaId" => EntityKind::Media,
+                "characterId" => EntityKind::Character,
+                "staffId" => EntityKind::Staff,
+                "studioId" => EntityKind::Studio,
+                _ => continue,
+            };
+            if let Some(id) = value.as_i64() {
+                self.invalidate_entity(kind, id).await.ok();
+            }
+        }
+    }
+
+    /// Returns false when the request is already being revalidated.
+    pub(crate) fn start_revalidation(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+    ) -> bool {
+        self.revalidating()
+            .insert(ResponseCache::key(query, variables, access_token))
+    }
+
+    pub(crate) fn finish_revalidation(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+    ) {
+        self.revalidating()
+            .remove(&ResponseCache::key(query, variables, access_token));
+    }
+
+    /// The requests of each token have their own entries, so that the response to one user is
+    /// never served to another one or to anonymous requests. Only a hash of the token is kept.
+    fn key(query: &str, variables: &Value, access_token: Option<&str>) -> String {
+        let key = queries::request_key(query, variables);
+        match access_token {
+            Some(access_token) => format!("{}\n{:016x}", key, queries::hash(access_token)),
+            None => key,
+        }
+    }
+
+    fn memory(&self) -> MutexGuard<'_, MemoryCache> {
+        self.memory
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn revalidating(&self) -> MutexGuard<'_, HashSet<String>> {
+        self.revalidating
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
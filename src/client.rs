@@ -1,202 +1,806 @@
-use crate::errors::GenericError;
-use crate::models::{Anime, Character, Manga};
+use crate::arguments::{CharacterArguments, MediaArguments};
+use crate::cache::{CachePolicy, ResponseCache};
+use crate::entity_store::EntityStore;
+use crate::errors::{Error, GraphQlError};
+use crate::in_flight::InFlightRequests;
+use crate::metrics::{Metrics, RequestRecord};
+use crate::models::{self, Anime, Character, Manga, ParseMode};
 use crate::queries;
-use reqwest::header::HeaderMap;
-use reqwest::header::HeaderValue;
+use crate::rate_limit::{Priority, RateLimit, RateLimiter};
+use crate::response::Response;
+use crate::retry::{CircuitBreaker, RetryPolicy};
+#[cfg(feature = "tracing")]
+use crate::telemetry;
+use crate::transport::{
+    RecordingTransport, ReplayTransport, ReqwestTransport, Transport, TransportRequest,
+    TransportResponse, DEFAULT_ENDPOINT,
+};
+use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 
 #[derive(Clone)]
-pub struct AniListClientBuilder<'a> {
-    reqwest_client: Option<reqwest::Client>,
-    anilist_token: Option<&'a str>,
+pub struct AniListClientBuilder {
+    transport: Option<Arc<dyn Transport>>,
+    endpoint: Option<String>,
+    headers: Vec<(String, String)>,
+    record_directory: Option<PathBuf>,
+    replay_directory: Option<PathBuf>,
+    anilist_token: Option<String>,
     timeout: Option<Duration>,
+    rate_limit_retries: Option<u32>,
+    rate_limit_pacing: bool,
+    retry_policy: Option<RetryPolicy>,
+    cache_policy: Option<CachePolicy>,
+    entity_store: bool,
+    request_coalescing: bool,
+    max_concurrency: Option<usize>,
+    metrics: Option<Arc<dyn Metrics>>,
+    parse_mode: ParseMode,
 }
 
-impl<'a> AniListClientBuilder<'a> {
+impl AniListClientBuilder {
     pub fn builder() -> Self {
         Self {
-            reqwest_client: None,
+            transport: None,
+            endpoint: None,
+            headers: Vec::new(),
+            record_directory: None,
+            replay_directory: None,
             anilist_token: None,
             timeout: None,
+            rate_limit_retries: None,
+            rate_limit_pacing: true,
+            retry_policy: None,
+            cache_policy: None,
+            entity_store: false,
+            request_coalescing: true,
+            max_concurrency: None,
+            metrics: None,
+            parse_mode: ParseMode::default(),
         }
     }
 
     pub fn with_reqwest_client(mut self, reqwest_client: reqwest::Client) -> Self {
-        self.reqwest_client = Some(reqwest_client);
+        self.transport = Some(Arc::new(ReqwestTransport::new(reqwest_client)));
 
         self
     }
 
     pub fn with_initialized_reqwest_client(mut self) -> Self {
-        self.reqwest_client = Some(reqwest::Client::new());
+        self.transport = Some(Arc::new(ReqwestTransport::default()));
 
         self
     }
 
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+
+        self
+    }
+
+    /// Writes every request and response to `directory`, see [`RecordingTransport`].
+    pub fn with_recording(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.record_directory = Some(directory.into());
+
+        self
+    }
+
+    /// Serves responses from `directory` instead of the network, see [`ReplayTransport`].
+    pub fn with_replay(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.replay_directory = Some(directory.into());
+
+        self
+    }
+
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        self.headers
+            .retain(|(header, _)| !header.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+
+        self
+    }
+
+    pub fn with_user_agent(self, user_agent: impl Into<String>) -> Self {
+        self.with_header("User-Agent", user_agent)
+    }
+
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
 
         self
     }
 
-    pub fn with_anilist_token(mut self, anilist_token: &'a str) -> Self {
-        self.anilist_token = Some(anilist_token);
+    pub fn with_rate_limit_retries(mut self, rate_limit_retries: u32) -> Self {
+        self.rate_limit_retries = Some(rate_limit_retries);
 
         self
     }
 
-    pub fn build(&self) -> Result<AniListClient<'a>, GenericError> {
-        if self.reqwest_client.is_none() {
-            return Err(GenericError(
-                "You have not filled in all the required elements in the builder: reqwest_client"
-                    .to_owned(),
-            ));
-        }
-        if self.anilist_token.is_none() {
-            return Err(GenericError(
-                "You have not filled in all the required elements in the builder: anilist_token"
-                    .to_owned(),
-            ));
-        }
-        if self.anilist_token.is_none() {
-            return Err(GenericError(
-                "You have not filled in all the required elements in the builder: anilist_token"
-                    .to_owned(),
-            ));
-        }
+    pub fn with_rate_limit_pacing(mut self, rate_limit_pacing: bool) -> Self {
+        self.rate_limit_pacing = rate_limit_pacing;
+
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+
+        self
+    }
+
+    /// Caches the responses of queries in memory, see [`ResponseCache`].
+    pub fn with_cache(mut self, cache_policy: CachePolicy) -> Self {
+        self.cache_policy = Some(cache_policy);
+
+        self
+    }
+
+    /// Merges the entities of every response into an [`EntityStore`].
+    pub fn with_entity_store(mut self, entity_store: bool) -> Self {
+        self.entity_store = entity_store;
+
+        self
+    }
+
+    /// Identical queries sent while one of them is running share its response, enabled by
+    /// default. Mutations are never coalesced.
+    pub fn with_request_coalescing(mut self, request_coalescing: bool) -> Self {
+        self.request_coalescing = request_coalescing;
+
+        self
+    }
+
+    /// The maximum number of requests sent at the same time, unlimited by default.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+
+        self
+    }
+
+    /// Reports every request to `metrics`, see [`crate::metrics::InMemoryMetrics`].
+    pub fn with_metrics(mut self, metrics: impl Metrics + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+
+        self
+    }
+
+    /// How [`AniListClient::get_anime`] and the other typed getters handle values that do not
+    /// match their models, lenient by default.
+    pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = parse_mode;
+
+        self
+    }
+
+    /// Only needed for operations that require authentication.
+    pub fn with_anilist_token(mut self, anilist_token: impl Into<String>) -> Self {
+        self.anilist_token = Some(anilist_token.into());
+
+        self
+    }
+
+    pub fn build(&self) -> Result<AniListClient, Error> {
+        // OAuth requests skip recording and replay, their responses hold tokens.
+        let http_transport: Arc<dyn Transport> =
+            match &self.transport {
+                Some(transport) => Arc::clone(transport),
+                None if self.replay_directory.is_some() => Arc::new(ReqwestTransport::default()),
+                None => return Err(Error::Builder(
+                    "You have not filled in all the required elements in the builder: transport"
+                        .to_owned(),
+                )),
+            };
+        let transport: Arc<dyn Transport> = match (&self.replay_directory, &self.record_directory) {
+            (Some(directory), _) => Arc::new(ReplayTransport::new(directory.clone())),
+            (None, Some(directory)) => Arc::new(RecordingTransport::new(
+                Arc::clone(&http_transport),
+                directory.clone(),
+            )),
+            (None, None) => Arc::clone(&http_transport),
+        };
 
         Ok(AniListClient {
-            reqwest_client: self.reqwest_client.clone().unwrap(),
-            anilist_token: self.anilist_token.unwrap(),
-            timeout: self.timeout.unwrap_or(Duration::from_secs(20)),
+            priority: Priority::default(),
+            inner: Arc::new(ClientInner {
+                transport,
+                http_transport,
+                endpoint: self
+                    .endpoint
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_ENDPOINT.to_owned()),
+                headers: self.headers.clone(),
+                anilist_token: self.anilist_token.clone(),
+                timeout: self.timeout.unwrap_or(Duration::from_secs(20)),
+                rate_limit_retries: self.rate_limit_retries.unwrap_or(3),
+                rate_limiter: RateLimiter::new(self.rate_limit_pacing),
+                retry_policy: self.retry_policy.clone().unwrap_or_default(),
+                circuit_breaker: CircuitBreaker::default(),
+                cache: self.cache_policy.clone().map(ResponseCache::new),
+                entities: self.entity_store.then(EntityStore::new),
+                in_flight: self.request_coalescing.then(InFlightRequests::new),
+                concurrency: self.max_concurrency.map(Semaphore::new),
+                metrics: self.metrics.clone(),
+                parse_mode: self.parse_mode,
+            }),
         })
     }
 }
 
+/// Cheap to clone, all clones share the same HTTP transport, rate limit and circuit breaker.
 #[derive(Clone)]
-pub struct AniListClient<'a> {
-    reqwest_client: reqwest::Client,
-    anilist_token: &'a str,
+pub struct AniListClient {
+    inner: Arc<ClientInner>,
+    priority: Priority,
+}
+
+#[derive(Debug, Default)]
+struct RequestStats {
+    retries: u32,
+    cache_hit: bool,
+}
+
+struct ClientInner {
+    transport: Arc<dyn Transport>,
+    /// The transport given to the builder, without recording or replay.
+    http_transport: Arc<dyn Transport>,
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    anilist_token: Option<String>,
     timeout: Duration,
+    rate_limit_retries: u32,
+    rate_limiter: RateLimiter,
+    retry_policy: RetryPolicy,
+    circuit_breaker: CircuitBreaker,
+    cache: Option<ResponseCache>,
+    entities: Option<EntityStore>,
+    in_flight: Option<InFlightRequests<Result<Response<Value>, Error>>>,
+    concurrency: Option<Semaphore>,
+    metrics: Option<Arc<dyn Metrics>>,
+    parse_mode: ParseMode,
 }
-impl<'a> AniListClient<'a> {
-    pub async fn get_anime(&self, variables: Value) -> Option<Anime> {
-        let data: Value = self
-            .request("anime", "get", variables, false, None)
+
+impl AniListClient {
+    pub(crate) fn http_transport(&self) -> &dyn Transport {
+        self.inner.http_transport.as_ref()
+    }
+
+    pub(crate) fn anilist_token(&self) -> Option<&str> {
+        self.inner.anilist_token.as_deref()
+    }
+
+    pub(crate) fn timeout(&self) -> Duration {
+        self.inner.timeout
+    }
+
+    /// A handle sending its requests with `priority`, sharing everything else with this client.
+    pub fn with_priority(&self, priority: Priority) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            priority,
+        }
+    }
+
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.inner.rate_limiter.current()
+    }
+
+    /// The cache enabled with [`AniListClientBuilder::with_cache`].
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.inner.cache.as_ref()
+    }
+
+    /// The store enabled with [`AniListClientBuilder::with_entity_store`].
+    pub fn entities(&self) -> Option<&EntityStore> {
+        self.inner.entities.as_ref()
+    }
+
+    pub async fn get_anime(&self, arguments: impl Into<MediaArguments>) -> Result<Anime, Error> {
+        self.get_anime_with_variables(arguments.into().to_variables())
             .await
-            .unwrap();
-        let mut anime: Anime = Anime::parse(&data["data"]["Media"]);
+    }
+
+    pub async fn get_anime_with_variables(&self, variables: Value) -> Result<Anime, Error> {
+        let response = self.request("anime", "get", variables, None).await?;
+        let mut anime: Anime = self.parse_root(response, "Media")?;
         anime.is_full_loaded = true;
 
-        Some(anime)
+        Ok(anime)
     }
 
-    pub async fn get_manga(&self, variables: Value) -> Option<Manga> {
-        let data = self
-            .request("manga", "get", variables, false, None)
+    pub async fn get_manga(&self, arguments: impl Into<MediaArguments>) -> Result<Manga, Error> {
+        self.get_manga_with_variables(arguments.into().to_variables())
             .await
-            .unwrap();
-        let mut manga = Manga::parse(&data["data"]["Media"]);
+    }
+
+    pub async fn get_manga_with_variables(&self, variables: Value) -> Result<Manga, Error> {
+        let response = self.request("manga", "get", variables, None).await?;
+        let mut manga: Manga = self.parse_root(response, "Media")?;
         manga.is_full_loaded = true;
 
-        Some(manga)
+        Ok(manga)
     }
 
-    pub async fn get_character(&self, variables: Value) -> Option<Character> {
-        let data = self
-            .request("character", "get", variables, false, None)
+    pub async fn get_character(
+        &self,
+        arguments: impl Into<CharacterArguments>,
+    ) -> Result<Character, Error> {
+        self.get_character_with_variables(arguments.into().to_variables())
             .await
-            .unwrap();
-        let mut character = Character::parse(&data["data"]["Character"]);
+    }
+
+    pub async fn get_character_with_variables(&self, variables: Value) -> Result<Character, Error> {
+        let response = self.request("character", "get", variables, None).await?;
+        let mut character: Character = self.parse_root(response, "Character")?;
         character.is_full_loaded = true;
 
-        Some(character)
+        Ok(character)
     }
 
+    #[deprecated(note = "use `AniListClient::session(access_token).set_progress` instead")]
     pub async fn set_progress(
         &self,
         new_chapter: i64,
         media_id: i64,
         access_token: &str,
-    ) -> Result<Value, GenericError> {
-        self.request(
-            "progress",
-            "set",
-            json!({"progress": new_chapter, "mediaId": media_id, "hidden": true}),
-            false,
-            Some(access_token),
-        )
-        .await
+    ) -> Result<Value, Error> {
+        self.session(access_token)
+            .set_progress(new_chapter, media_id)
+            .await
     }
 
+    #[deprecated(
+        note = "use `AniListClient::session(access_token).set_increment_progress` instead"
+    )]
     pub async fn set_increment_progress(
         &self,
         progress_start: i64,
         progress_end: i64,
         media_id: i64,
         access_token: &str,
-    ) -> Result<Value, GenericError> {
-        self
-            .request(
-                "progress_increment",
-                "set",
-                json!({"progress_start": progress_start, "progress_end": progress_end, "mediaId": media_id, "hidden": true}),
-                false,
-                Some(access_token),
-            )
+    ) -> Result<Value, Error> {
+        self.session(access_token)
+            .set_increment_progress(progress_start, progress_end, media_id)
+            .await
+    }
+
+    /// Runs any GraphQL query or mutation, with the client token when there is one, and
+    /// deserializes its `data`.
+    pub async fn execute<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, Error> {
+        self.execute_partial(query, variables).await?.into_result()
+    }
+
+    /// Like [`AniListClient::execute`], but keeps the data when AniList also returned errors.
+    pub async fn execute_partial<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<Response<T>, Error> {
+        self.execute_with_token(query, variables, self.inner.anilist_token.as_deref())
             .await
     }
 
-    async fn request(
+    pub(crate) async fn execute_with_token<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+        access_token: Option<&str>,
+    ) -> Result<Response<T>, Error> {
+        let response = self.request_query(query, variables, access_token).await?;
+
+        Ok(Response {
+            data: response.data.map(AniListClient::decode_data).transpose()?,
+            errors: response.errors,
+        })
+    }
+
+    pub(crate) async fn request(
         &self,
         media_type: &str,
         action: &str,
         variables: Value,
-        need_auth: bool,
         access_token: Option<&str>,
-    ) -> Result<Value, GenericError> {
+    ) -> Result<Response<Value>, Error> {
         let query: &str = if let Some(query) = AniListClient::get_query(media_type, action) {
             query
         } else {
-            return Err(GenericError(
-                "The type of query entered is not available.".to_owned(),
-            ));
+            return Err(Error::UnsupportedQuery {
+                media_type: media_type.to_owned(),
+                action: action.to_owned(),
+            });
+        };
+
+        self.request_query(query, variables, access_token).await
+    }
+
+    /// Runs a query or mutation, reporting it to the metrics and, with the `tracing` feature,
+    /// in an `anilist.request` span. Variables that look like secrets are not logged.
+    async fn request_query(
+        &self,
+        query: &str,
+        variables: Value,
+        access_token: Option<&str>,
+    ) -> Result<Response<Value>, Error> {
+        let started = Instant::now();
+        let operation = queries::operation_name(query);
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "anilist.request",
+            operation = %operation,
+            variables = %telemetry::redact(&variables),
+            priority = ?self.priority,
+            success = tracing::field::Empty,
+            status = tracing::field::Empty,
+            retries = tracing::field::Empty,
+            cache_hit = tracing::field::Empty,
+            rate_limit_remaining = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        );
+
+        let mut stats = RequestStats::default();
+        let run = self.run_query(query, variables, access_token, &mut stats);
+        #[cfg(feature = "tracing")]
+        let result = tracing::Instrument::instrument(run, span.clone()).await;
+        #[cfg(not(feature = "tracing"))]
+        let result = run.await;
+
+        let record = RequestRecord {
+            operation,
+            duration: started.elapsed(),
+            retries: stats.retries,
+            cache_hit: stats.cache_hit,
+            success: result.is_ok(),
+            status: result.as_ref().err().and_then(Error::status),
+            rate_limit_remaining: self.rate_limit().map(|rate_limit| rate_limit.remaining),
+        };
+        #[cfg(feature = "tracing")]
+        telemetry::record(&span, &record, result.as_ref().err());
+        if let Some(metrics) = &self.inner.metrics {
+            metrics.record(&record);
+        }
+
+        result
+    }
+
+    async fn run_query(
+        &self,
+        query: &str,
+        variables: Value,
+        access_token: Option<&str>,
+        stats: &mut RequestStats,
+    ) -> Result<Response<Value>, Error> {
+        let authenticated = access_token.is_some();
+        let cache = self
+            .inner
+            .cache
+            .as_ref()
+            .filter(|_| !ResponseCache::bypasses(query, authenticated));
+        let cached = match cache {
+            Some(cache) => cache.get(query, &variables, access_token).await,
+            None => None,
+        };
+        if let (Some(cached), Some(cache)) = (&cached, cache) {
+            if cached.is_fresh() {
+                stats.cache_hit = true;
+                return Ok(Response::from_data(cached.data.clone()));
+            }
+            if cache.serves_stale_while_revalidating() {
+                stats.cache_hit = true;
+                self.revalidate(query, &variables, access_token);
+                return Ok(Response::from_data(cached.data.clone()));
+            }
+        }
+
+        let fetch = self.fetch(
+            query,
+            &variables,
+            access_token,
+            cache.is_some(),
+            &mut stats.retries,
+        );
+        let result = match &self.inner.in_flight {
+            Some(in_flight) if !queries::is_mutation(query) => {
+                let key = format!(
+                    "{}\n{}\n{:?}",
+                    queries::request_key(query, &variables),
+                    access_token.unwrap_or_default(),
+                    self.priority
+                );
+                in_flight.run(key, fetch).await
+            }
+            _ => fetch.await,
+        };
+        match (result, cache, cached) {
+            (Ok(response), _, _) => Ok(response),
+            (Err(error), Some(cache), Some(cached)) if cache.serves_stale_on(&error) => {
+                stats.cache_hit = true;
+                Ok(Response::from_data(cached.data))
+            }
+            (Err(error), _, _) => Err(error),
+        }
+    }
+
+    async fn fetch(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+        cacheable: bool,
+        retries: &mut u32,
+    ) -> Result<Response<Value>, Error> {
+        let response = self
+            .send_with_retries(query, variables, access_token, retries)
+            .await?;
+        self.record_response(query, variables, access_token, &response, cacheable)
+            .await;
+
+        Ok(response)
+    }
+
+    /// Refreshes a stale entry in the background, at most once at a time per request.
+    fn revalidate(&self, query: &str, variables: &Value, access_token: Option<&str>) {
+        let Some(cache) = &self.inner.cache else {
+            return;
+        };
+        if !cache.start_revalidation(query, variables, access_token) {
+            return;
+        }
+
+        let client = self.clone();
+        let query = query.to_owned();
+        let variables = variables.clone();
+        let access_token = access_token.map(str::to_owned);
+        tokio::spawn(async move {
+            client
+                .fetch(&query, &variables, access_token.as_deref(), true, &mut 0)
+                .await
+                .ok();
+            if let Some(cache) = &client.inner.cache {
+                cache.finish_revalidation(&query, &variables, access_token.as_deref());
+            }
+        });
+    }
+
+    /// Feeds a response from the network to the entity store and the cache. `cacheable` is false
+    /// when the request bypasses the cache, mutations still invalidate it.
+    async fn record_response(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+        response: &Response<Value>,
+        cacheable: bool,
+    ) {
+        if let (Some(entities), Some(data)) = (&self.inner.entities, &response.data) {
+            entities.merge(data);
+        }
+        let Some(cache) = &self.inner.cache else {
+            return;
         };
+
+        if queries::is_mutation(query) {
+            cache.invalidate_mutation(variables).await;
+        } else if let (Some(data), true) = (&response.data, cacheable) {
+            if response.errors.is_empty() {
+                cache.insert(query, variables, access_token, data).await;
+            }
+        }
+    }
+
+    async fn send_with_retries(
+        &self,
+        query: &str,
+        variables: &Value,
+        access_token: Option<&str>,
+        retries: &mut u32,
+    ) -> Result<Response<Value>, Error> {
         let json: Value = json!({"query": query, "variables": variables});
-        let mut headers = HeaderMap::new();
-        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
-        headers.insert("Accept", HeaderValue::from_static("application/json"));
+
+        let mutation = queries::is_mutation(query);
+        let mut rate_limited_attempts: u32 = 0;
+        let mut attempt: u32 = 1;
+        loop {
+            self.inner.circuit_breaker.check()?;
+            self.inner.rate_limiter.acquire(self.priority).await;
+            let permit = match &self.inner.concurrency {
+                Some(concurrency) => concurrency.acquire().await.ok(),
+                None => None,
+            };
+            let result = self.send(&json, access_token).await;
+            drop(permit);
+            self.inner
+                .circuit_breaker
+                .record(&result, &self.inner.retry_policy);
+            match result {
+                Err(Error::RateLimited { .. })
+                    if rate_limited_attempts < self.inner.rate_limit_retries =>
+                {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("AniList rate limit exceeded, waiting for the next window");
+                    rate_limited_attempts += 1;
+                    *retries += 1;
+                }
+                Err(error)
+                    if self
+                        .inner
+                        .retry_policy
+                        .should_retry(&error, attempt, mutation) =>
+                {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(attempt, error = %error, "Retrying the AniList request");
+                    tokio::time::sleep(self.inner.retry_policy.delay(attempt)).await;
+                    attempt += 1;
+                    *retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        json: &Value,
+        access_token: Option<&str>,
+    ) -> Result<Response<Value>, Error> {
+        let mut headers: Vec<(String, String)> = vec![
+            ("Content-Type".to_owned(), "application/json".to_owned()),
+            ("Accept".to_owned(), "application/json".to_owned()),
+            (
+                "User-Agent".to_owned(),
+                concat!("rust-anilist/", env!("CARGO_PKG_VERSION")).to_owned(),
+            ),
+        ];
+        for (name, value) in self.inner.headers.iter() {
+            headers.retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+            headers.push((name.clone(), value.clone()));
+        }
         if let Some(access_token) = access_token {
-            let header_value = format!("Bearer {}", access_token).parse().unwrap();
-            headers.insert("Authorization", header_value);
-        }
-
-        let mut body = self
-            .reqwest_client
-            .post("https://graphql.anilist.co/")
-            .headers(headers)
-            .timeout(self.timeout)
-            .body(json.to_string());
-        if need_auth {
-            body = body.bearer_auth(self.anilist_token);
-        }
-
-        let response: String = body.send().await?.text().await?;
-        let result = serde_json::from_str(&response);
-        let result_value: Value = if let Ok(result_value) = result {
-            result_value
-        } else if let Err(result_error) = result {
-            return Err(GenericError(format!("serde_json error: {}", result_error)));
-        } else {
-            return Err(GenericError("Unknown error".to_owned()));
+            headers.push((
+                "Authorization".to_owned(),
+                format!("Bearer {}", access_token),
+            ));
+        }
+
+        let response: TransportResponse = self
+            .inner
+            .transport
+            .send(TransportRequest {
+                url: self.inner.endpoint.clone(),
+                headers,
+                body: json.to_string(),
+                timeout: self.inner.timeout,
+            })
+            .await?;
+        let status = response.status;
+        let is_success = (200..300).contains(&status);
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        self.inner.rate_limiter.update(
+            AniListClient::get_header_number(&response, "X-RateLimit-Limit"),
+            AniListClient::get_header_number(&response, "X-RateLimit-Remaining"),
+            AniListClient::get_header_number(&response, "X-RateLimit-Reset"),
+        );
+        let response: String = response.body;
+
+        if status == 429 {
+            self.inner.rate_limiter.exhausted(retry_after);
+            return Err(Error::RateLimited { retry_after });
+        }
+
+        let mut result_value: Value = match serde_json::from_str(&response) {
+            Ok(result_value) => result_value,
+            Err(_) if !is_success => {
+                return Err(Error::Http {
+                    status,
+                    body: response,
+                });
+            }
+            Err(error) => return Err(Error::decode("", error)),
         };
 
-        Ok(result_value)
+        let errors: Vec<GraphQlError> = match result_value.get("errors") {
+            Some(errors) if !errors.is_null() => serde_json::from_value(errors.clone())
+                .map_err(|error| Error::decode("errors", error))?,
+            _ => Vec::new(),
+        };
+        let data = result_value
+            .get_mut("data")
+            .map(Value::take)
+            .unwrap_or_default();
+        let has_data = data
+            .as_object()
+            .is_some_and(|data| data.values().any(|value| !value.is_null()));
+
+        if !has_data {
+            if errors.iter().any(|error| error.status == Some(404)) {
+                return Err(Error::NotFound);
+            }
+            if errors.iter().any(|error| error.status == Some(429)) {
+                self.inner.rate_limiter.exhausted(retry_after);
+                return Err(Error::RateLimited { retry_after });
+            }
+            if !errors.is_empty() {
+                return Err(Error::GraphQl(errors));
+            }
+            if !is_success {
+                return Err(Error::Http {
+                    status,
+                    body: response,
+                });
+            }
+        }
+
+        Ok(Response {
+            data: (!data.is_null()).then_some(data),
+            errors,
+        })
+    }
+
+    fn get_header_number<T: std::str::FromStr>(
+        response: &TransportResponse,
+        name: &str,
+    ) -> Option<T> {
+        response
+            .header(name)
+            .and_then(|value| value.trim().parse().ok())
+    }
+
+    fn decode_data<T: DeserializeOwned>(data: Value) -> Result<T, Error> {
+        serde_path_to_error::deserialize(data).map_err(|error| {
+            Error::decode(error.path().to_string(), error.into_inner()).within("data")
+        })
+    }
+
+    /// The entity at `field` of the response of a typed getter. Errors on its nested fields, e.g.
+    /// a deleted relation, do not fail the getter when the entity itself was returned.
+    fn parse_root<T: DeserializeOwned>(
+        &self,
+        response: Response<Value>,
+        field: &str,
+    ) -> Result<T, Error> {
+        match &response.data {
+            Some(data) if !data[field].is_null() => self.parse_data(data, field),
+            _ => self.parse_data(&response.into_result()?, field),
+        }
+    }
+
+    /// Parses the model under the root `field` of `data`, errors name their path from `data`, e.g.
+    /// `data.Media.characters.nodes[3].name.alternative`.
+    fn parse_data<T: DeserializeOwned>(&self, data: &Value, field: &str) -> Result<T, Error> {
+        let path = format!("data.{}", field);
+        match data.get(field) {
+            Some(Value::Null) | None => Err(Error::NotFound),
+            Some(value) if value.is_object() => models::from_value(value, self.inner.parse_mode)
+                .map_err(|error| error.within(&path)),
+            Some(_) => Err(Error::decode(
+                path,
+                serde::de::Error::custom("expected an object"),
+            )),
+        }
     }
 
-    pub fn get_query(media_type: &str, action: &str) -> Option<&'a str> {
-        const MEDIA_TYPES: [&str; 9] = [
+    pub fn get_query(media_type: &str, action: &str) -> Option<&'static str> {
+        const MEDIA_TYPES: [&str; 13] = [
             "anime",
             "manga",
             "character",
@@ -206,6 +810,10 @@ impl<'a> AniListClient<'a> {
             "progress",
             "mediasids",
             "progress_increment",
+            "viewer",
+            "notifications",
+            "favourite",
+            "list_entry",
         ];
         if !MEDIA_TYPES.contains(&media_type) {
             return None;
@@ -219,6 +827,11 @@ impl<'a> AniListClient<'a> {
             ("get", "mediasids") => Some(queries::get_mediasids::GET_MEDIAS_IDS),
             ("set", "progress") => Some(queries::set_progress::SET_PROGRESS),
             ("set", "progress_increment") => Some(queries::set_progress::SET_PROGRESS_INCREMENT),
+            ("get", "viewer") => Some(queries::get_viewer::GET_VIEWER),
+            ("get", "notifications") => Some(queries::get_notifications::GET_NOTIFICATIONS),
+            ("set", "favourite") => Some(queries::toggle_favourite::TOGGLE_FAVOURITE),
+            ("set", "list_entry") => Some(queries::media_list_entry::SAVE_MEDIA_LIST_ENTRY),
+            ("delete", "list_entry") => Some(queries::media_list_entry::DELETE_MEDIA_LIST_ENTRY),
             _ => None,
         }
     }
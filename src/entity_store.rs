@@ -0,0 +1,232 @@
+use crate::models::{self, Anime, Character, Manga, ParseMode, Person, Studio};
+use crate::queries;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EntityKind {
+    Media,
+    Character,
+    Staff,
+    Studio,
+}
+
+impl EntityKind {
+    /// The kind of the entities found under `field`, for the fields that hold entities.
+    pub(crate) fn of_field(field: &str) -> Option<Self> {
+        match field {
+            "Media"
+            | "media"
+            | "anime"
+            | "manga"
+            | "relations"
+            | "mediaRecommendation"
+            | "characterMedia"
+            | "staffMedia" => Some(EntityKind::Media),
+            "Character" | "characters" => Some(EntityKind::Character),
+            "Staff" | "staff" | "voiceActors" => Some(EntityKind::Staff),
+            "Studio" | "studios" => Some(EntityKind::Studio),
+            _ => None,
+        }
+    }
+}
+
+/// Every media, character, staff and studio seen in the responses of a client, merged by id, so
+/// that an entity only partially selected by one query can be completed by another.
+#[derive(Debug, Default)]
+pub struct EntityStore {
+    entities: Mutex<HashMap<(EntityKind, i64), Map<String, Value>>>,
+}
+
+impl EntityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entities().clear();
+    }
+
+    pub fn remove(&self, kind: EntityKind, id: i64) {
+        self.entities().remove(&(kind, id));
+    }
+
+    /// Merges the entities of the `data` of a response, fields of later responses win.
+    pub fn merge(&self, data: &Value) {
+        let mut entities = self.entities();
+        visit_entities(data, &mut |kind, id, object| {
+            merge_object(entities.entry((kind, id)).or_default(), object);
+        });
+    }
+
+    /// Everything known about the entity, with the field names of the API.
+    pub fn get(&self, kind: EntityKind, id: i64) -> Option<Value> {
+        self.entities()
+            .get(&(kind, id))
+            .map(|object| Value::Object(object.clone()))
+    }
+
+    /// The fields AniList returned for the entity, including those it returned as `null`.
+    pub fn populated_fields(&self, kind: EntityKind, id: i64) -> Vec<String> {
+        let mut fields: Vec<String> = self
+            .entities()
+            .get(&(kind, id))
+            .map(|object| object.keys().cloned().collect())
+            .unwrap_or_default();
+        fields.sort();
+
+        fields
+    }
+
+    /// Whether `path`, e.g. `title.english` or `nextAiringEpisode`, was returned for the entity.
+    pub fn is_populated(&self, kind: EntityKind, id: i64, path: &str) -> bool {
+        let entities = self.entities();
+        let Some(mut object) = entities.get(&(kind, id)) else {
+            return false;
+        };
+
+        let mut fields = path.split('.').peekable();
+        while let Some(field) = fields.next() {
+            let Some(value) = object.get(field) else {
+                return false;
+            };
+            if fields.peek().is_none() {
+                return true;
+            }
+            match value.as_object() {
+                Some(child) => object = child,
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// The anime, marked as fully loaded when every field of [`crate::AniListClient::get_anime`]
+    /// is known.
+    pub fn anime(&self, id: i64) -> Option<Anime> {
+        let data = self.media_of_type(id, "ANIME")?;
+        let mut anime: Anime = models::from_value(&data, ParseMode::Lenient).ok()?;
+        anime.is_full_loaded = is_complete(&data, queries::get_anime::GET_ANIME);
+
+        Some(anime)
+    }
+
+    pub fn manga(&self, id: i64) -> Option<Manga> {
+        let data = self.media_of_type(id, "MANGA")?;
+        let mut manga: Manga = models::from_value(&data, ParseMode::Lenient).ok()?;
+        manga.is_full_loaded = is_complete(&data, queries::get_manga::GET_MANGA);
+
+        Some(manga)
+    }
+
+    pub fn character(&self, id: i64) -> Option<Character> {
+        let data = self.get(EntityKind::Character, id)?;
+        let mut character: Character = models::from_value(&data, ParseMode::Lenient).ok()?;
+        character.is_full_loaded = is_complete(&data, queries::get_character::GET_CHARACTER);
+
+        Some(character)
+    }
+
+    pub fn person(&self, id: i64) -> Option<Person> {
+        let data = self.get(EntityKind::Staff, id)?;
+        let mut person: Person = models::from_value(&data, ParseMode::Lenient).ok()?;
+        person.is_full_loaded = is_complete(&data, queries::get_person::GET_PERSON);
+
+        Some(person)
+    }
+
+    pub fn studio(&self, id: i64) -> Option<Studio> {
+        let data = self.get(EntityKind::Studio, id)?;
+
+        models::from_value(&data, ParseMode::Lenient).ok()
+    }
+
+    /// Media whose type is unknown are returned as both anime and manga.
+    fn media_of_type(&self, id: i64, media_type: &str) -> Option<Value> {
+        self.get(EntityKind::Media, id).filter(|data| {
+            data["type"]
+                .as_str()
+                .is_none_or(|found| found == media_type)
+        })
+    }
+
+    fn entities(&self) -> MutexGuard<'_, HashMap<(EntityKind, i64), Map<String, Value>>> {
+        self.entities
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+/// Calls `visit` with every object that has an id and sits under a field holding entities.
+pub(crate) fn visit_entities(
+    value: &Value,
+    visit: &mut impl FnMut(EntityKind, i64, &Map<String, Value>),
+) {
+    visit_value(value, None, None, visit);
+}
+
+/// `kind` is the kind of `value` itself, `connection` the kind of the `nodes` and `node` below
+/// it, so that the ids of edges are not mistaken for the ids of their nodes.
+fn visit_value(
+    value: &Value,
+    kind: Option<EntityKind>,
+    connection: Option<EntityKind>,
+    visit: &mut impl FnMut(EntityKind, i64, &Map<String, Value>),
+) {
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                visit_value(value, kind, connection, visit);
+            }
+        }
+        Value::Object(object) => {
+            if let (Some(kind), Some(id)) = (kind, object.get("id").and_then(Value::as_i64)) {
+                visit(kind, id, object);
+            }
+            for (field, value) in object {
+                match (EntityKind::of_field(field), field.as_str()) {
+                    (Some(kind), _) => visit_value(value, Some(kind), Some(kind), visit),
+                    (None, "nodes" | "node") => visit_value(value, connection, connection, visit),
+                    (None, "edges") => visit_value(value, None, connection, visit),
+                    (None, _) => visit_value(value, None, None, visit),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Nested objects such as `title` are merged field by field, connections to other entities are
+/// replaced since their nodes are stored on their own.
+fn merge_object(target: &mut Map<String, Value>, source: &Map<String, Value>) {
+    for (field, value) in source {
+        match (target.get_mut(field), value) {
+            (Some(Value::Object(target)), Value::Object(source))
+                if EntityKind::of_field(field).is_none() =>
+            {
+                merge_object(target, source)
+            }
+            _ => {
+                target.insert(field.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Whether `data` has every field selected on the root entity of `query`.
+fn is_complete(data: &Value, query: &str) -> bool {
+    queries::root_selection(query)
+        .iter()
+        .all(|field| data.get(field).is_some())
+}
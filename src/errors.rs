@@ -1,16 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
 use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 
-#[derive(Debug)]
-pub struct GenericError(pub String);
+#[derive(Debug, Clone)]
+pub enum Error {
+    Builder(String),
+    MissingToken,
+    TokenExpired {
+        user_id: i64,
+    },
+    Io(Arc<std::io::Error>),
+    UnsupportedQuery {
+        media_type: String,
+        action: String,
+    },
+    Transport(Arc<dyn StdError + Send + Sync>),
+    Timeout,
+    Http {
+        status: u16,
+        body: String,
+    },
+    GraphQl(Vec<GraphQlError>),
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    Unavailable {
+        retry_after: Duration,
+    },
+    NotFound,
+    Decode {
+        path: String,
+        source: Arc<serde_json::Error>,
+    },
+}
+
+impl Error {
+    pub(crate) fn decode(path: impl Into<String>, source: serde_json::Error) -> Self {
+        Error::Decode {
+            path: path.into(),
+            source: Arc::new(source),
+        }
+    }
 
-impl From<reqwest::Error> for GenericError {
+    /// Prefixes the path of a decode error with the path of the value it was decoded from, e.g.
+    /// `name.alternative` within `data.Media` becomes `data.Media.name.alternative`.
+    #[cfg(feature = "client")]
+    pub(crate) fn within(self, parent: &str) -> Self {
+        match self {
+            Error::Decode { path, source } => Error::Decode {
+                path: match path.as_str() {
+                    "" | "." => parent.to_owned(),
+                    path if path.starts_with('[') => format!("{}{}", parent, path),
+                    path => format!("{}.{}", parent, path),
+                },
+                source,
+            },
+            error => error,
+        }
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Http { status, .. } => Some(*status),
+            Error::RateLimited { .. } => Some(429),
+            Error::Unavailable { .. } => Some(503),
+            Error::NotFound => Some(404),
+            Error::GraphQl(errors) => errors.iter().find_map(|error| error.status),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(Arc::new(error))
+    }
+}
+
+#[cfg(feature = "client")]
+impl From<reqwest::Error> for Error {
     fn from(error: reqwest::Error) -> Self {
-        GenericError(format!("reqwest Error: {}", error))
+        if error.is_timeout() {
+            Error::Timeout
+        } else if error.is_decode() {
+            Error::Decode {
+                path: String::new(),
+                source: Arc::new(serde::de::Error::custom(error)),
+            }
+        } else {
+            Error::Transport(Arc::new(error))
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Builder(message) => write!(f, "{}", message),
+            Error::MissingToken => write!(f, "This operation requires an AniList token."),
+            Error::TokenExpired { user_id } => {
+                write!(f, "The AniList token of user {} has expired.", user_id)
+            }
+            Error::Io(error) => write!(f, "I/O error: {}", error),
+            Error::UnsupportedQuery { media_type, action } => write!(
+                f,
+                "The type of query entered is not available: {} {}.",
+                action, media_type
+            ),
+            Error::Transport(error) => write!(f, "Transport error: {}", error),
+            Error::Timeout => write!(f, "The request to AniList timed out."),
+            Error::Http { status, .. } => write!(f, "AniList responded with HTTP {}.", status),
+            Error::GraphQl(errors) => {
+                write!(f, "AniList returned GraphQL errors: ")?;
+                for (index, error) in errors.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+
+                Ok(())
+            }
+            Error::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "AniList rate limit exceeded, retry after {}s.",
+                retry_after.as_secs()
+            ),
+            Error::RateLimited { retry_after: None } => {
+                write!(f, "AniList rate limit exceeded.")
+            }
+            Error::Unavailable { retry_after } => write!(
+                f,
+                "AniList is unavailable, probably in maintenance, retry after {}s.",
+                retry_after.as_secs()
+            ),
+            Error::NotFound => write!(f, "The requested resource was not found on AniList."),
+            Error::Decode { path, source } if path.is_empty() => {
+                write!(f, "Failed to decode the AniList response: {}", source)
+            }
+            Error::Decode { path, source } => write!(
+                f,
+                "Failed to decode the AniList response at `{}`: {}",
+                path, source
+            ),
+        }
     }
 }
 
-impl fmt::Display for GenericError {
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Transport(error) => Some(error.as_ref()),
+            Error::Io(error) => Some(error.as_ref()),
+            Error::Decode { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphQlError {
+    pub message: String,
+    #[serde(default)]
+    pub locations: Vec<Location>,
+    #[serde(default)]
+    pub path: Vec<PathSegment>,
+    #[serde(default)]
+    pub status: Option<u16>,
+}
+
+impl fmt::Display for GraphQlError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.message)?;
+        if !self.path.is_empty() {
+            write!(f, " (at ")?;
+            for (index, segment) in self.path.iter().enumerate() {
+                match segment {
+                    PathSegment::Field(field) if index > 0 => write!(f, ".{}", field)?,
+                    PathSegment::Field(field) => write!(f, "{}", field)?,
+                    PathSegment::Index(position) => write!(f, "[{}]", position)?,
+                }
+            }
+            write!(f, ")")?;
+        }
+
+        Ok(())
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Index(usize),
+    Field(String),
+}
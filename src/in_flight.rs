@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::OnceCell;
+
+/// Shares the result of a request with the identical requests started while it is running. If
+/// the caller running it is dropped, one of the waiting callers runs its own request instead.
+#[derive(Debug)]
+pub(crate) struct InFlightRequests<T> {
+    requests: Mutex<HashMap<String, Arc<OnceCell<T>>>>,
+}
+
+impl<T: Clone> InFlightRequests<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) async fn run(&self, key: String, request: impl Future<Output = T>) -> T {
+        let cell = Arc::clone(self.requests().entry(key.clone()).or_default());
+        let result = cell.get_or_init(|| request).await.clone();
+
+        let mut requests = self.requests();
+        if requests
+            .get(&key)
+            .is_some_and(|current| Arc::ptr_eq(current, &cell))
+        {
+            requests.remove(&key);
+        }
+
+        result
+    }
+
+    fn requests(&self) -> MutexGuard<'_, HashMap<String, Arc<OnceCell<T>>>> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
@@ -1,7 +1,52 @@
+#[cfg(feature = "client")]
+mod arguments;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "client")]
+mod cache;
+#[cfg(feature = "client")]
 mod client;
+#[cfg(feature = "client")]
+pub mod entity_store;
 mod errors;
+#[cfg(feature = "client")]
+mod in_flight;
+#[cfg(feature = "client")]
+pub mod metrics;
 pub mod models;
+#[cfg(feature = "client")]
+pub mod oauth;
+#[cfg(feature = "client")]
 mod queries;
+#[cfg(feature = "client")]
+mod rate_limit;
+#[cfg(feature = "client")]
+mod response;
+#[cfg(feature = "client")]
+mod retry;
+#[cfg(feature = "client")]
+mod session;
+#[cfg(feature = "tracing")]
+mod telemetry;
+#[cfg(feature = "test-server")]
+pub mod testing;
+#[cfg(feature = "client")]
+pub mod token_store;
+#[cfg(feature = "client")]
+pub mod transport;
 
+#[cfg(feature = "client")]
+pub use self::arguments::{CharacterArguments, MediaArguments};
+#[cfg(feature = "client")]
+pub use self::cache::{CachePolicy, ResponseCache};
+#[cfg(feature = "client")]
 pub use self::client::{AniListClient, AniListClientBuilder};
-pub use self::errors::GenericError;
+pub use self::errors::{Error, GraphQlError, Location, PathSegment};
+#[cfg(feature = "client")]
+pub use self::rate_limit::{Priority, RateLimit};
+#[cfg(feature = "client")]
+pub use self::response::Response;
+#[cfg(feature = "client")]
+pub use self::retry::RetryPolicy;
+#[cfg(feature = "client")]
+pub use self::session::{FavouriteTarget, Session};
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The upper bounds of the buckets of [`Histogram`], the last bucket has no bound.
+const LATENCY_BUCKETS: [Duration; 10] = [
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// One call of a query or mutation, as seen by its caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestRecord {
+    /// The root fields of the operation, e.g. `Media` or `Page`.
+    pub operation: String,
+    pub duration: Duration,
+    pub retries: u32,
+    pub cache_hit: bool,
+    pub success: bool,
+    /// The status of the error, when it has one.
+    pub status: Option<u16>,
+    pub rate_limit_remaining: Option<u32>,
+}
+
+/// Receives a [`RequestRecord`] for every request of a client.
+pub trait Metrics: Send + Sync {
+    fn record(&self, record: &RequestRecord);
+}
+
+impl<T: Metrics + ?Sized> Metrics for Arc<T> {
+    fn record(&self, record: &RequestRecord) {
+        (**self).record(record)
+    }
+}
+
+/// Counters and latency histograms per operation, kept in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+    operations: Mutex<HashMap<String, OperationMetrics>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationMetrics {
+    pub requests: u64,
+    pub errors: u64,
+    pub cache_hits: u64,
+    pub retries: u64,
+    pub latency: Histogram,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    counts: [u64; LATENCY_BUCKETS.len() + 1],
+    sum: Duration,
+}
+
+impl InMemoryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn operation(&self, operation: &str) -> Option<OperationMetrics> {
+        self.operations().get(operation).cloned()
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, OperationMetrics> {
+        self.operations().clone()
+    }
+
+    pub fn reset(&self) {
+        self.operations().clear();
+    }
+
+    fn operations(&self) -> std::sync::MutexGuard<'_, HashMap<String, OperationMetrics>> {
+        self.operations
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl Metrics for InMemoryMetrics {
+    fn record(&self, record: &RequestRecord) {
+        let mut operations = self.operations();
+        let metrics = operations.entry(record.operation.clone()).or_default();
+        metrics.requests += 1;
+        metrics.errors += u64::from(!record.success);
+        metrics.cache_hits += u64::from(record.cache_hit);
+        metrics.retries += u64::from(record.retries);
+        metrics.latency.observe(record.duration);
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            counts: [0; LATENCY_BUCKETS.len() + 1],
+            sum: Duration::ZERO,
+        }
+    }
+}
+
+impl Histogram {
+    pub fn observe(&mut self, duration: Duration) {
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| duration <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.counts[bucket] += 1;
+        self.sum += duration;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        let count = self.count();
+
+        (count > 0).then(|| self.sum.div_f64(count as f64))
+    }
+
+    /// `(upper bound, count)` of each bucket, the last bucket has no upper bound.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        LATENCY_BUCKETS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain(std::iter::once(None))
+            .zip(self.counts.iter().copied())
+    }
+}
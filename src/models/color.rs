@@ -3,7 +3,9 @@
 
 use serde::{Deserialize, Serialize};
 
+/// The named colors of user profiles, or the hex code of any other color.
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Color {
     Blue,
     #[default]
@@ -15,3 +17,33 @@ pub enum Color {
     Gray,
     Hex(String),
 }
+
+impl From<String> for Color {
+    fn from(color: String) -> Self {
+        match color.as_str() {
+            "blue" => Color::Blue,
+            "purple" => Color::Purple,
+            "pink" => Color::Pink,
+            "orange" => Color::Orange,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "gray" => Color::Gray,
+            _ => Color::Hex(color),
+        }
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Blue => "blue".to_owned(),
+            Color::Purple => "purple".to_owned(),
+            Color::Pink => "pink".to_owned(),
+            Color::Orange => "orange".to_owned(),
+            Color::Red => "red".to_owned(),
+            Color::Green => "green".to_owned(),
+            Color::Gray => "gray".to_owned(),
+            Color::Hex(hex) => hex,
+        }
+    }
+}
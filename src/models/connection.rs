@@ -0,0 +1,62 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+struct Nodes<T> {
+    #[serde(default)]
+    nodes: T,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Edges<T> {
+    #[serde(default)]
+    edges: T,
+}
+
+/// `#[serde(with)]` for connections listing their entities, e.g. `characters { nodes { ... } }`.
+pub(crate) mod nodes {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer, T: Serialize>(
+        nodes: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Nodes { nodes }.serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + Default,
+    {
+        Option::<Nodes<T>>::deserialize(deserializer).map(|connection| {
+            connection
+                .map(|connection| connection.nodes)
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// `#[serde(with)]` for connections listing the edges to their entities, e.g.
+/// `relations { edges { relationType node { ... } } }`.
+pub(crate) mod edges {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer, T: Serialize>(
+        edges: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Edges { edges }.serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + Default,
+    {
+        Option::<Edges<T>>::deserialize(deserializer).map(|connection| {
+            connection
+                .map(|connection| connection.edges)
+                .unwrap_or_default()
+        })
+    }
+}
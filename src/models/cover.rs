@@ -5,6 +5,7 @@ use crate::models::Color;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Cover {
     pub extra_large: Option<String>,
     pub large: Option<String>,
@@ -4,6 +4,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Format {
     #[default]
     Tv,
@@ -16,4 +17,18 @@ pub enum Format {
     Manga,
     Novel,
     OneShot,
+    Unknown(String),
 }
+
+api_enum!(Format {
+    Tv => "TV",
+    TvShort => "TV_SHORT",
+    Movie => "MOVIE",
+    Special => "SPECIAL",
+    Ova => "OVA",
+    Ona => "ONA",
+    Music => "MUSIC",
+    Manga => "MANGA",
+    Novel => "NOVEL",
+    OneShot => "ONE_SHOT",
+});
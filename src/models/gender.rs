@@ -4,6 +4,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Gender {
     Male,
     Female,
@@ -16,3 +17,25 @@ impl Default for Gender {
         Gender::Other(String::from("Neutral"))
     }
 }
+
+impl From<String> for Gender {
+    fn from(gender: String) -> Self {
+        match gender.to_ascii_lowercase().as_str() {
+            "male" => Gender::Male,
+            "female" => Gender::Female,
+            "non-binary" | "nonbinary" => Gender::NonBinary,
+            _ => Gender::Other(gender),
+        }
+    }
+}
+
+impl From<Gender> for String {
+    fn from(gender: Gender) -> Self {
+        match gender {
+            Gender::Male => "Male".to_owned(),
+            Gender::Female => "Female".to_owned(),
+            Gender::NonBinary => "Non-binary".to_owned(),
+            Gender::Other(gender) => gender,
+        }
+    }
+}
@@ -5,6 +5,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Language {
     #[default]
     Japanese,
@@ -33,4 +34,34 @@ pub enum Language {
     Nepali,
     Hindi,
     Urdu,
+    Unknown(String),
 }
+
+api_enum!(Language {
+    Japanese => "Japanese",
+    English => "English",
+    Korean => "Korean",
+    Italian => "Italian",
+    Spanish => "Spanish",
+    Portuguese => "Portuguese",
+    French => "French",
+    German => "German",
+    Hebrew => "Hebrew",
+    Hungarian => "Hungarian",
+    Chinese => "Chinese",
+    Arabic => "Arabic",
+    Filipino => "Filipino",
+    Catalan => "Catalan",
+    Finnish => "Finnish",
+    Turkish => "Turkish",
+    Dutch => "Dutch",
+    Swedish => "Swedish",
+    Thai => "Thai",
+    Tagalog => "Tagalog",
+    Malaysian => "Malaysian",
+    Indonesian => "Indonesian",
+    Vietnamese => "Vietnamese",
+    Nepali => "Nepali",
+    Hindi => "Hindi",
+    Urdu => "Urdu",
+});
@@ -1,18 +1,23 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::nullable;
 use crate::models::Color;
 use crate::models::Language;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Link {
     pub id: Option<i64>,
     pub title: Option<String>,
     pub thumbnail: Option<String>,
+    #[serde(deserialize_with = "nullable")]
     pub url: String,
+    #[serde(deserialize_with = "nullable")]
     pub site: String,
     pub site_id: Option<i64>,
+    #[serde(rename = "type")]
     pub link_type: Option<Type>,
     pub language: Option<Language>,
     pub color: Option<Color>,
@@ -20,9 +25,17 @@ pub struct Link {
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Type {
     #[default]
     Info,
     Streaming,
     Social,
+    Unknown(String),
 }
+
+api_enum!(Type {
+    Info => "INFO",
+    Streaming => "STREAMING",
+    Social => "SOCIAL",
+});
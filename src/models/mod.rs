@@ -1,6 +1,48 @@
+/// Implements `FromStr`, `Display` and the (de)serialization through strings of an enum of the
+/// AniList API from the string of each variant. Other strings are kept in its `Unknown` variant.
+macro_rules! api_enum {
+    ($name:ident { $($variant:ident => $value:literal,)+ }) => {
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from(value.to_owned()))
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                match self {
+                    $($name::$variant => f.write_str($value),)+
+                    $name::Unknown(value) => f.write_str(value),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                match value.as_str() {
+                    $($value => $name::$variant,)+
+                    _ => $name::Unknown(value),
+                }
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                match value {
+                    $name::Unknown(value) => value,
+                    value => value.to_string(),
+                }
+            }
+        }
+    };
+}
+
 pub mod anime;
 pub mod character;
 pub mod color;
+mod connection;
 pub mod cover;
 pub mod date;
 pub mod format;
@@ -40,7 +82,6 @@ pub use person::Person;
 pub use relation::{Relation, Type as RelationType};
 pub use score::{Format as ScoreFormat, Score};
 pub use season::Season;
-use serde::{Deserialize, Serialize};
 pub use source::Source;
 pub use status::Status;
 pub use studio::Studio;
@@ -48,10 +89,110 @@ pub use tag::Tag;
 pub use title::Title;
 pub use user::User;
 
-#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+use crate::errors::Error;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use serde_path_to_error::{Path, Segment};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum MediaType {
     Anime,
     Manga,
+    Unknown(String),
+}
+
+api_enum!(MediaType {
+    Anime => "ANIME",
+    Manga => "MANGA",
+});
+
+/// The type of media whose type was not returned.
+impl Default for MediaType {
+    fn default() -> Self {
+        MediaType::Unknown(String::new())
+    }
+}
+
+/// How the models handle values that do not match the type of their field.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Fails with the path of the first mismatching value.
+    Strict,
+    /// Mismatching values are replaced by their default, as if AniList had not returned them.
+    /// Entities nested in `nodes` or `edges` lists are dropped when their `id` mismatches, other
+    /// lists take their default as a whole when one of their elements mismatches.
     #[default]
-    Unknown,
+    Lenient,
+}
+
+/// Deserializes a model from the camelCase JSON of AniList. Fields missing from `value` take
+/// their default in both modes, so models can be built from partial selections, except the `id`
+/// of media, characters, staff and studios. Errors name the path of the value that failed, e.g.
+/// `characters.nodes[3].name.alternative`.
+pub fn from_value<T: DeserializeOwned>(value: &Value, mode: ParseMode) -> Result<T, Error> {
+    let mut value = Cow::Borrowed(value);
+    loop {
+        let error = match serde_path_to_error::deserialize(value.as_ref()) {
+            Ok(model) => return Ok(model),
+            Err(error) => error,
+        };
+
+        if mode == ParseMode::Strict || !remove(value.to_mut(), error.path()) {
+            return Err(Error::decode(error.path().to_string(), error.into_inner()));
+        }
+    }
+}
+
+/// Removes the value at `path`. Only the entities of `nodes` and `edges` lists are removed from
+/// their list, other lists are removed as a whole since their elements may be positional, e.g.
+/// `yearsActive`. Returns false when the path does not lead to a removable value, e.g. for the
+/// root or for values only known by their parent.
+fn remove(value: &mut Value, path: &Path) -> bool {
+    let mut segments: Vec<&Segment> = path
+        .iter()
+        .take_while(|segment| matches!(segment, Segment::Seq { .. } | Segment::Map { .. }))
+        .collect();
+    while let [.., list, Segment::Seq { .. }] = segments.as_slice() {
+        if matches!(list, Segment::Map { key } if key == "nodes" || key == "edges") {
+            break;
+        }
+        segments.pop();
+    }
+    let Some((last, parents)) = segments.split_last() else {
+        return false;
+    };
+
+    let mut parent = value;
+    for segment in parents {
+        let child = match (segment, parent) {
+            (Segment::Seq { index }, Value::Array(values)) => values.get_mut(*index),
+            (Segment::Map { key }, Value::Object(object)) => object.get_mut(key),
+            _ => None,
+        };
+        match child {
+            Some(child) => parent = child,
+            None => return false,
+        }
+    }
+
+    match (last, parent) {
+        (Segment::Seq { index }, Value::Array(values)) if *index < values.len() => {
+            values.remove(*index);
+            true
+        }
+        (Segment::Map { key }, Value::Object(object)) => object.remove(key).is_some(),
+        _ => false,
+    }
+}
+
+/// Deserializes `null` as the default value, AniList returns `null` for most unset fields.
+pub(crate) fn nullable<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Option::<T>::deserialize(deserializer).map(Option::unwrap_or_default)
 }
@@ -1,16 +1,22 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::nullable;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Name {
+    #[serde(deserialize_with = "nullable")]
     pub first: String,
     pub middle: Option<String>,
     pub last: Option<String>,
+    #[serde(deserialize_with = "nullable")]
     pub full: String,
     pub native: Option<String>,
+    #[serde(deserialize_with = "nullable")]
     pub alternative: Vec<String>,
+    #[serde(deserialize_with = "nullable")]
     pub alternative_spoiler: Vec<String>,
     pub user_preferred: Option<String>,
 }
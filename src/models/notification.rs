@@ -7,12 +7,15 @@ use serde::{Deserialize, Serialize};
 pub struct Notification {}
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct NotificationOption {
+    #[serde(rename = "type")]
     notification_type: Type,
     enabled: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Type {
     #[default]
     ActivityMessage,
@@ -29,4 +32,22 @@ pub enum Type {
     MediaDataChange,
     MediaMerge,
     MediaDeletion,
+    Unknown(String),
 }
+
+api_enum!(Type {
+    ActivityMessage => "ACTIVITY_MESSAGE",
+    ActivityReply => "ACTIVITY_REPLY",
+    Following => "FOLLOWING",
+    ActivityMention => "ACTIVITY_MENTION",
+    ThreadCommentMention => "THREAD_COMMENT_MENTION",
+    Airing => "AIRING",
+    ActivityLike => "ACTIVITY_LIKE",
+    ActivityReplyLike => "ACTIVITY_REPLY_LIKE",
+    ThreadLike => "THREAD_LIKE",
+    ActivityReplySubscribed => "ACTIVITY_REPLY_SUBSCRIBED",
+    RelatedMediaAddition => "RELATED_MEDIA_ADDITION",
+    MediaDataChange => "MEDIA_DATA_CHANGE",
+    MediaMerge => "MEDIA_MERGE",
+    MediaDeletion => "MEDIA_DELETION",
+});
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Occupation {
     Animator,
     Arranger,
@@ -29,4 +33,99 @@ pub enum Occupation {
     Writer,
     #[default]
     None,
+    Unknown(String),
+}
+
+impl Occupation {
+    /// The occupation as AniList writes it, `None` has no name.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Occupation::Animator => "Animator",
+            Occupation::Arranger => "Arranger",
+            Occupation::Artist => "Artist",
+            Occupation::AudioEngineer => "Audio Engineer",
+            Occupation::BackgroundArtist => "Background Artist",
+            Occupation::Band => "Band",
+            Occupation::CGArtist => "CG Artist",
+            Occupation::Composer => "Composer",
+            Occupation::CompositeArtist => "Composite Artist",
+            Occupation::Designer => "Designer",
+            Occupation::Director => "Director",
+            Occupation::Editor => "Editor",
+            Occupation::Illustrator => "Illustrator",
+            Occupation::Lyricist => "Lyricist",
+            Occupation::Mangaka => "Mangaka",
+            Occupation::Musician => "Musician",
+            Occupation::Painter => "Painter",
+            Occupation::Producer => "Producer",
+            Occupation::ProductionManager => "Production Manager",
+            Occupation::ScriptWriter => "Script Writer",
+            Occupation::StoryBoardArtist => "Storyboard Artist",
+            Occupation::Translator => "Translator",
+            Occupation::Vocalist => "Vocalist",
+            Occupation::VoiceActor => "Voice Actor",
+            Occupation::Writer => "Writer",
+            Occupation::None => "",
+            Occupation::Unknown(occupation) => occupation,
+        }
+    }
+}
+
+impl FromStr for Occupation {
+    type Err = Infallible;
+
+    fn from_str(occupation: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(occupation.to_owned()))
+    }
+}
+
+impl fmt::Display for Occupation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// AniList occupations are free text, e.g. `Voice Actor`, so they are compared without case and
+/// spaces.
+impl From<String> for Occupation {
+    fn from(occupation: String) -> Self {
+        match occupation.to_uppercase().replace(' ', "").as_str() {
+            "ANIMATOR" => Occupation::Animator,
+            "ARRANGER" => Occupation::Arranger,
+            "ARTIST" => Occupation::Artist,
+            "AUDIOENGINEER" => Occupation::AudioEngineer,
+            "BACKGROUNDARTIST" => Occupation::BackgroundArtist,
+            "BAND" => Occupation::Band,
+            "CGARTIST" => Occupation::CGArtist,
+            "COMPOSER" => Occupation::Composer,
+            "COMPOSITEARTIST" => Occupation::CompositeArtist,
+            "DESIGNER" => Occupation::Designer,
+            "DIRECTOR" => Occupation::Director,
+            "EDITOR" => Occupation::Editor,
+            "ILLUSTRATOR" => Occupation::Illustrator,
+            "LYRICIST" => Occupation::Lyricist,
+            "MANGAKA" => Occupation::Mangaka,
+            "MUSICIAN" => Occupation::Musician,
+            "PAINTER" => Occupation::Painter,
+            "PRODUCER" => Occupation::Producer,
+            "PRODUCTIONMANAGER" => Occupation::ProductionManager,
+            "SCRIPTWRITER" => Occupation::ScriptWriter,
+            "STORYBOARDARTIST" => Occupation::StoryBoardArtist,
+            "TRANSLATOR" => Occupation::Translator,
+            "VOCALIST" => Occupation::Vocalist,
+            "VOICEACTOR" => Occupation::VoiceActor,
+            "WRITER" => Occupation::Writer,
+            "" => Occupation::None,
+            _ => Occupation::Unknown(occupation),
+        }
+    }
+}
+
+impl From<Occupation> for String {
+    fn from(occupation: Occupation) -> Self {
+        match occupation {
+            Occupation::Unknown(occupation) => occupation,
+            occupation => occupation.as_str().to_owned(),
+        }
+    }
 }
@@ -1,15 +1,21 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::nullable;
 use crate::models::Anime;
 use crate::models::Manga;
 use crate::models::MediaType;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
 
 // TODO: Use generic type
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Edge", into = "Edge")]
 pub struct Relation {
     pub media_type: MediaType,
+    /// Also holds the media of types unknown to the crate, with the fields they share with anime.
     pub anime: Option<Anime>,
     pub manga: Option<Manga>,
     pub id: i64,
@@ -18,6 +24,7 @@ pub struct Relation {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Type {
     Adaptation,
     Prequel,
@@ -32,4 +39,176 @@ pub enum Type {
     Source,
     Compilation,
     Contains,
+    Unknown(String),
+}
+
+api_enum!(Type {
+    Adaptation => "ADAPTATION",
+    Prequel => "PREQUEL",
+    Sequel => "SEQUEL",
+    Parent => "PARENT",
+    SideStory => "SIDE_STORY",
+    Character => "CHARACTER",
+    Summary => "SUMMARY",
+    Alternative => "ALTERNATIVE",
+    SpinOff => "SPIN_OFF",
+    Other => "OTHER",
+    Source => "SOURCE",
+    Compilation => "COMPILATION",
+    Contains => "CONTAINS",
+});
+
+/// The type of relations whose type was not returned.
+impl Default for Type {
+    fn default() -> Self {
+        Type::Unknown(String::new())
+    }
+}
+
+/// A relation as AniList returns it, an edge to the related media.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct Edge {
+    id: i64,
+    #[serde(deserialize_with = "nullable")]
+    relation_type: Type,
+    #[serde(deserialize_with = "nullable")]
+    is_main_studio: bool,
+    node: Option<Node>,
+}
+
+enum Node {
+    Anime(Box<Anime>),
+    Manga(Box<Manga>),
+    Other(String, Box<Anime>),
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (media_type, media) = match self {
+            Node::Anime(anime) => ("ANIME", serde_json::to_value(anime)),
+            Node::Manga(manga) => ("MANGA", serde_json::to_value(manga)),
+            Node::Other(media_type, media) => (media_type.as_str(), serde_json::to_value(media)),
+        };
+        let mut media = media.map_err(ser::Error::custom)?;
+        media["type"] = Value::from(media_type);
+
+        media.serialize(serializer)
+    }
+}
+
+/// The node is deserialized as an anime while its `type` and the fields only known by manga are
+/// set aside, then turned into a manga when it is one. Unlike an internally tagged enum, which
+/// buffers the node, this keeps the path of the values that fail, e.g.
+/// `relations.edges[0].node.title.native`.
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(NodeVisitor)
+    }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+    type Value = Node;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an anime or a manga")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Node, A::Error> {
+        let mut node = NodeMap {
+            map,
+            media_type: None,
+            chapters: None,
+            volumes: None,
+        };
+        let anime = Anime::deserialize(de::value::MapAccessDeserializer::new(&mut node))?;
+
+        match node.media_type.map(MediaType::from) {
+            Some(MediaType::Anime) => Ok(Node::Anime(Box::new(anime))),
+            Some(MediaType::Manga) => {
+                let mut manga: Manga = serde_json::to_value(anime)
+                    .and_then(serde_json::from_value)
+                    .map_err(de::Error::custom)?;
+                manga.chapters = node.chapters;
+                manga.volumes = node.volumes;
+
+                Ok(Node::Manga(Box::new(manga)))
+            }
+            Some(MediaType::Unknown(media_type)) => Ok(Node::Other(media_type, Box::new(anime))),
+            None => Err(de::Error::missing_field("type")),
+        }
+    }
+}
+
+struct NodeMap<A> {
+    map: A,
+    media_type: Option<String>,
+    chapters: Option<i64>,
+    volumes: Option<i64>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for NodeMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        while let Some(key) = self.map.next_key::<String>()? {
+            match key.as_str() {
+                "type" => self.media_type = self.map.next_value()?,
+                "chapters" => self.chapters = self.map.next_value()?,
+                "volumes" => self.volumes = self.map.next_value()?,
+                _ => return seed.deserialize(key.into_deserializer()).map(Some),
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        self.map.next_value_seed(seed)
+    }
+}
+
+impl From<Edge> for Relation {
+    fn from(edge: Edge) -> Self {
+        let (media_type, anime, manga) = match edge.node {
+            Some(Node::Anime(anime)) => (MediaType::Anime, Some(*anime), None),
+            Some(Node::Manga(manga)) => (MediaType::Manga, None, Some(*manga)),
+            Some(Node::Other(media_type, media)) => {
+                (MediaType::Unknown(media_type), Some(*media), None)
+            }
+            None => (MediaType::default(), None, None),
+        };
+
+        Relation {
+            media_type,
+            anime,
+            manga,
+            id: edge.id,
+            relation_type: edge.relation_type,
+            is_main_studio: edge.is_main_studio,
+        }
+    }
+}
+
+impl From<Relation> for Edge {
+    fn from(relation: Relation) -> Self {
+        Edge {
+            id: relation.id,
+            relation_type: relation.relation_type,
+            is_main_studio: relation.is_main_studio,
+            node: match (relation.media_type, relation.anime, relation.manga) {
+                (MediaType::Unknown(media_type), Some(media), _) if !media_type.is_empty() => {
+                    Some(Node::Other(media_type, Box::new(media)))
+                }
+                (_, Some(anime), _) => Some(Node::Anime(Box::new(anime))),
+                (_, None, Some(manga)) => Some(Node::Manga(Box::new(manga))),
+                (_, None, None) => None,
+            },
+        }
+    }
 }
@@ -1,15 +1,20 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::nullable;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Score {
+    #[serde(rename = "averageScore", deserialize_with = "nullable")]
     pub average: i64,
+    #[serde(rename = "meanScore", deserialize_with = "nullable")]
     pub mean: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Format {
     Point100,
     #[default]
@@ -17,4 +22,13 @@ pub enum Format {
     Point10,
     Point5,
     Point3,
+    Unknown(String),
 }
+
+api_enum!(Format {
+    Point100 => "POINT_100",
+    Point10Decimal => "POINT_10_DECIMAL",
+    Point10 => "POINT_10",
+    Point5 => "POINT_5",
+    Point3 => "POINT_3",
+});
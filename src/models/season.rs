@@ -4,9 +4,18 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Season {
     Winter,
     Spring,
     Summer,
     Fall,
+    Unknown(String),
 }
+
+api_enum!(Season {
+    Winter => "WINTER",
+    Spring => "SPRING",
+    Summer => "SUMMER",
+    Fall => "FALL",
+});
@@ -4,6 +4,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Source {
     #[default]
     Original,
@@ -21,4 +22,23 @@ pub enum Source {
     Comic,
     MultimediaProject,
     PictureBook,
+    Unknown(String),
 }
+
+api_enum!(Source {
+    Original => "ORIGINAL",
+    Manga => "MANGA",
+    LightNovel => "LIGHT_NOVEL",
+    VisualNovel => "VISUAL_NOVEL",
+    VideoGame => "VIDEO_GAME",
+    Other => "OTHER",
+    Novel => "NOVEL",
+    Doujinshi => "DOUJINSHI",
+    Anime => "ANIME",
+    WebNovel => "WEB_NOVEL",
+    LiveAction => "LIVE_ACTION",
+    Game => "GAME",
+    Comic => "COMIC",
+    MultimediaProject => "MULTIMEDIA_PROJECT",
+    PictureBook => "PICTURE_BOOK",
+});
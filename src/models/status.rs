@@ -4,6 +4,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Status {
     Finished,
     Releasing,
@@ -17,4 +18,19 @@ pub enum Status {
     Dropped,
     Paused,
     Repeating,
+    Unknown(String),
 }
+
+api_enum!(Status {
+    Finished => "FINISHED",
+    Releasing => "RELEASING",
+    NotYetReleased => "NOT_YET_RELEASED",
+    Cancelled => "CANCELLED",
+    Hiatus => "HIATUS",
+    Current => "CURRENT",
+    Planning => "PLANNING",
+    Completed => "COMPLETED",
+    Dropped => "DROPPED",
+    Paused => "PAUSED",
+    Repeating => "REPEATING",
+});
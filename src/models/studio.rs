@@ -1,38 +1,25 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::nullable;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Studio {
     pub id: i64,
+    #[serde(default)]
     pub name: String,
+    #[serde(default)]
     pub is_animation_studio: bool,
+    #[serde(default, rename = "siteUrl", deserialize_with = "nullable")]
     pub url: String,
     pub is_favourite: Option<bool>,
+    #[serde(default, deserialize_with = "nullable")]
     pub favourites: i64,
 }
 
 impl Studio {
-    pub(crate) fn parse(data: &serde_json::Value, studio: Option<Studio>) -> Self {
-        let mut studio = studio.unwrap_or_default();
-
-        studio.id = data["id"].as_i64().unwrap_or_default();
-        data["name"]
-            .as_str()
-            .unwrap_or_default()
-            .clone_into(&mut studio.name);
-        studio.is_animation_studio = data["isAnimationStudio"].as_bool().unwrap_or_default();
-        data["siteUrl"]
-            .as_str()
-            .unwrap_or_default()
-            .clone_into(&mut studio.url);
-        studio.is_favourite = data["isFavourite"].as_bool();
-        studio.favourites = data["favourites"].as_i64().unwrap_or_default();
-
-        studio
-    }
-
     // pub async fn get_medias<T>() -> Result<T> {
     //     todo!()
     // }
@@ -1,17 +1,25 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::nullable;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Tag {
     pub id: i64,
     pub name: String,
+    #[serde(deserialize_with = "nullable")]
     pub description: String,
+    #[serde(deserialize_with = "nullable")]
     pub category: String,
+    #[serde(deserialize_with = "nullable")]
     pub rank: i64,
+    #[serde(deserialize_with = "nullable")]
     pub is_general_spoiler: bool,
+    #[serde(deserialize_with = "nullable")]
     pub is_media_spoiler: bool,
+    #[serde(deserialize_with = "nullable")]
     pub is_adult: bool,
     pub user_id: Option<i64>,
 }
@@ -1,12 +1,15 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::nullable;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Title {
     pub romaji: Option<String>,
     pub english: Option<String>,
+    #[serde(deserialize_with = "nullable")]
     pub native: String,
     pub user_preferred: Option<String>,
 }
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2022 Andriel Ferreira <https://github.com/AndrielFR>
 
+use crate::models::connection::nodes;
 use crate::models::Anime;
 use crate::models::Character;
 use crate::models::Color;
@@ -9,17 +10,19 @@ use crate::models::Image;
 use crate::models::Manga;
 use crate::models::NotificationOption;
 use crate::models::Person;
+use crate::models::ScoreFormat;
 use crate::models::Status;
 use crate::models::Studio;
-use crate::models::{Score, ScoreFormat};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct User {
     id: i32,
     name: String,
     about: Option<String>,
     avatar: Option<Image>,
+    #[serde(rename = "bannerImage")]
     banner: Option<String>,
     is_following: Option<bool>,
     is_follower: Option<bool>,
@@ -31,11 +34,13 @@ pub struct User {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 struct Options {
     title_language: UserTitleLanguage,
     display_adult_content: bool,
     airing_notifications: bool,
     profile_color: Color,
+    #[serde(rename = "notificationOptions")]
     notifications_options: Vec<NotificationOption>,
     timezone: String,
     activity_merge_time: i32,
@@ -45,6 +50,7 @@ struct Options {
 }
 
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum UserTitleLanguage {
     #[default]
     Romaji,
@@ -53,23 +59,43 @@ pub enum UserTitleLanguage {
     RomajiStylised,
     EnglishStylised,
     NativeStylised,
+    Unknown(String),
 }
 
+api_enum!(UserTitleLanguage {
+    Romaji => "ROMAJI",
+    English => "ENGLISH",
+    Native => "NATIVE",
+    RomajiStylised => "ROMAJI_STYLISED",
+    EnglishStylised => "ENGLISH_STYLISED",
+    NativeStylised => "NATIVE_STYLISED",
+});
+
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum UserStaffNameLanguage {
     RomajiWestern,
     #[default]
     Romaji,
     Native,
+    Unknown(String),
 }
 
+api_enum!(UserStaffNameLanguage {
+    RomajiWestern => "ROMAJI_WESTERN",
+    Romaji => "ROMAJI",
+    Native => "NATIVE",
+});
+
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct ListActivityOption {
     status: Status,
     disabled: bool,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct MediaListOptions {
     score_format: ScoreFormat,
     row_order: String,
@@ -78,6 +104,7 @@ pub struct MediaListOptions {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct MediaListTypeOptions {
     section_order: Vec<String>,
     split_completed_section_by_format: bool,
@@ -87,24 +114,32 @@ pub struct MediaListTypeOptions {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct Favourites {
+    #[serde(with = "nodes")]
     anime: Vec<Anime>,
+    #[serde(with = "nodes")]
     manga: Vec<Manga>,
+    #[serde(with = "nodes")]
     characters: Vec<Character>,
+    #[serde(with = "nodes")]
     staff: Vec<Person>,
+    #[serde(with = "nodes")]
     studios: Vec<Studio>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct UserStatisticTypes {
     anime: UserStatistics,
     manga: UserStatistics,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct UserStatistics {
     count: i32,
-    score: Score,
+    mean_score: f32,
     standard_deviation: f32,
     minutes_watched: Option<i32>,
     episodes_watched: Option<i32>,
@@ -115,9 +150,10 @@ pub struct UserStatistics {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct UserFormatStatistic {
     count: i32,
-    score: Score,
+    mean_score: f32,
     minutes_watched: Option<i32>,
     chapters_read: Option<i32>,
     media_ids: Vec<i32>,
@@ -125,9 +161,10 @@ pub struct UserFormatStatistic {
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct UserStatusStatistic {
     count: i32,
-    score: Score,
+    mean_score: f32,
     minutes_watched: Option<i32>,
     chapters_read: Option<i32>,
     media_ids: Vec<i32>,
@@ -0,0 +1,222 @@
+use crate::errors::Error;
+use crate::transport::TransportRequest;
+use crate::AniListClient;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const AUTHORIZE_URL: &str = "https://anilist.co/api/v2/oauth/authorize";
+pub const TOKEN_URL: &str = "https://anilist.co/api/v2/oauth/token";
+
+/// The credentials of an AniList API client, see <https://anilist.co/settings/developer>.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+}
+
+impl ClientCredentials {
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: redirect_uri.into(),
+        }
+    }
+
+    /// The URL to send the user to for the authorization code grant.
+    pub fn authorization_url(&self) -> String {
+        authorization_code_url(&self.client_id, &self.redirect_uri)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessToken {
+    pub access_token: String,
+    #[serde(default)]
+    pub token_type: String,
+    #[serde(default, deserialize_with = "deserialize_number")]
+    pub expires_in: Option<u64>,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+}
+
+impl AccessToken {
+    pub fn claims(&self) -> Result<TokenClaims, Error> {
+        decode_token(&self.access_token)
+    }
+}
+
+/// What AniList puts in its JWT access tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenClaims {
+    pub user_id: i64,
+    pub issued_at: Option<SystemTime>,
+    pub expires_at: SystemTime,
+}
+
+impl TokenClaims {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= SystemTime::now()
+    }
+
+    pub fn expires_within(&self, duration: Duration) -> bool {
+        SystemTime::now()
+            .checked_add(duration)
+            .is_none_or(|limit| self.expires_at <= limit)
+    }
+}
+
+pub fn authorization_code_url(client_id: &str, redirect_uri: &str) -> String {
+    url::Url::parse_with_params(
+        AUTHORIZE_URL,
+        &[
+            ("client_id", client_id),
+            ("redirect_uri", redirect_uri),
+            ("response_type", "code"),
+        ],
+    )
+    .map(String::from)
+    .unwrap_or_default()
+}
+
+pub fn implicit_grant_url(client_id: &str) -> String {
+    url::Url::parse_with_params(
+        AUTHORIZE_URL,
+        &[("client_id", client_id), ("response_type", "token")],
+    )
+    .map(String::from)
+    .unwrap_or_default()
+}
+
+/// Reads the token from the redirect of the implicit grant. Accepts the whole redirect URL or
+/// only its fragment, like `access_token=...&token_type=Bearer&expires_in=31536000`.
+pub fn parse_implicit_redirect(redirect: &str) -> Result<AccessToken, Error> {
+    let fragment = redirect
+        .split_once('#')
+        .map(|(_, fragment)| fragment)
+        .unwrap_or(redirect);
+    let mut token = AccessToken {
+        access_token: String::new(),
+        token_type: String::new(),
+        expires_in: None,
+        refresh_token: None,
+    };
+
+    for (key, value) in url::form_urlencoded::parse(fragment.as_bytes()) {
+        match key.as_ref() {
+            "access_token" => token.access_token = value.into_owned(),
+            "token_type" => token.token_type = value.into_owned(),
+            "expires_in" => token.expires_in = value.parse().ok(),
+            "refresh_token" => token.refresh_token = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    if token.access_token.is_empty() {
+        return Err(invalid("access_token", "missing from the redirect"));
+    }
+
+    Ok(token)
+}
+
+/// Reads the user id and expiry of an AniList access token. The signature is not verified.
+pub fn decode_token(access_token: &str) -> Result<TokenClaims, Error> {
+    let payload = access_token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| invalid("access_token", "not a JWT"))?;
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|error| invalid("access_token", error))?;
+    let claims: Value =
+        serde_json::from_slice(&payload).map_err(|error| Error::decode("access_token", error))?;
+
+    let user_id = match &claims["sub"] {
+        Value::String(sub) => sub.parse().ok(),
+        sub => sub.as_i64(),
+    }
+    .ok_or_else(|| invalid("access_token.sub", "missing user id"))?;
+    let expires_at = claims["exp"]
+        .as_f64()
+        .ok_or_else(|| invalid("access_token.exp", "missing expiry"))
+        .and_then(|exp| timestamp("access_token.exp", exp))?;
+    let issued_at = claims["iat"]
+        .as_f64()
+        .map(|iat| timestamp("access_token.iat", iat))
+        .transpose()?;
+
+    Ok(TokenClaims {
+        user_id,
+        issued_at,
+        expires_at,
+    })
+}
+
+impl AniListClient {
+    /// Exchanges the code of the authorization code grant for an access token. The exchange
+    /// goes through the transport given to the builder and is never recorded nor replayed.
+    pub async fn exchange_code(
+        &self,
+        credentials: &ClientCredentials,
+        code: &str,
+    ) -> Result<AccessToken, Error> {
+        let body = json!({
+            "grant_type": "authorization_code",
+            "client_id": credentials.client_id,
+            "client_secret": credentials.client_secret,
+            "redirect_uri": credentials.redirect_uri,
+            "code": code,
+        });
+        let response = self
+            .http_transport()
+            .send(TransportRequest {
+                url: TOKEN_URL.to_owned(),
+                headers: vec![
+                    ("Content-Type".to_owned(), "application/json".to_owned()),
+                    ("Accept".to_owned(), "application/json".to_owned()),
+                ],
+                body: body.to_string(),
+                timeout: self.timeout(),
+            })
+            .await?;
+
+        if !(200..300).contains(&response.status) {
+            return Err(Error::Http {
+                status: response.status,
+                body: response.body,
+            });
+        }
+
+        let deserializer = &mut serde_json::Deserializer::from_str(&response.body);
+        serde_path_to_error::deserialize(deserializer)
+            .map_err(|error| Error::decode(error.path().to_string(), error.into_inner()))
+    }
+}
+
+/// Fails on timestamps a `SystemTime` cannot hold, e.g. `1e300`, instead of panicking.
+fn timestamp(path: &str, seconds: f64) -> Result<SystemTime, Error> {
+    Duration::try_from_secs_f64(seconds.max(0.0))
+        .ok()
+        .and_then(|duration| UNIX_EPOCH.checked_add(duration))
+        .ok_or_else(|| invalid(path, format!("timestamp out of range: {}", seconds)))
+}
+
+fn invalid(path: &str, message: impl std::fmt::Display) -> Error {
+    Error::decode(path, serde::de::Error::custom(message))
+}
+
+fn deserialize_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::Number(number)) => number.as_u64(),
+        Some(Value::String(number)) => number.parse().ok(),
+        _ => None,
+    })
+}
@@ -1,6 +1,6 @@
 pub const GET_ANIME: &str = r#"
-query ($id: Int, $id_mal: Int) {
-  Media (id: $id, idMal: $id_mal, type: ANIME) {
+query ($id: Int, $id_mal: Int, $search: String, $is_adult: Boolean, $season_year: Int) {
+  Media (id: $id, idMal: $id_mal, search: $search, isAdult: $is_adult, seasonYear: $season_year, type: ANIME) {
     id
     idMal
     title {
@@ -1,6 +1,6 @@
 pub const GET_CHARACTER: &str = r#"
-query ($id: Int) {
- Character (id: $id) {
+query ($id: Int, $search: String) {
+ Character (id: $id, search: $search) {
    id
    name {
      first
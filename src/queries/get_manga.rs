@@ -1,6 +1,6 @@
 pub const GET_MANGA: &str = r#"
-query ($id: Int, $id_mal: Int) {
-  Media (id: $id, idMal: $id_mal, type: MANGA) {
+query ($id: Int, $id_mal: Int, $search: String, $is_adult: Boolean, $season_year: Int) {
+  Media (id: $id, idMal: $id_mal, search: $search, isAdult: $is_adult, seasonYear: $season_year, type: MANGA) {
     id
     idMal
     title {
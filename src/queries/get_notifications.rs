@@ -0,0 +1,118 @@
+pub const GET_NOTIFICATIONS: &str = r#"
+query ($page: Int, $per_page: Int, $reset: Boolean) {
+  Page(page: $page, perPage: $per_page) {
+    pageInfo {
+      total
+      currentPage
+      lastPage
+      hasNextPage
+      perPage
+    }
+    notifications(resetNotificationCount: $reset) {
+      ... on AiringNotification {
+        id
+        type
+        episode
+        contexts
+        createdAt
+        media {
+          id
+          type
+          title {
+            userPreferred
+          }
+        }
+      }
+      ... on RelatedMediaAdditionNotification {
+        id
+        type
+        context
+        createdAt
+        media {
+          id
+          type
+          title {
+            userPreferred
+          }
+        }
+      }
+      ... on MediaDataChangeNotification {
+        id
+        type
+        context
+        reason
+        createdAt
+        media {
+          id
+          type
+          title {
+            userPreferred
+          }
+        }
+      }
+      ... on MediaDeletionNotification {
+        id
+        type
+        context
+        reason
+        deletedMediaTitle
+        createdAt
+      }
+      ... on FollowingNotification {
+        id
+        type
+        context
+        createdAt
+        user {
+          id
+          name
+        }
+      }
+      ... on ActivityMessageNotification {
+        id
+        type
+        context
+        activityId
+        createdAt
+        user {
+          id
+          name
+        }
+      }
+      ... on ActivityMentionNotification {
+        id
+        type
+        context
+        activityId
+        createdAt
+        user {
+          id
+          name
+        }
+      }
+      ... on ActivityReplyNotification {
+        id
+        type
+        context
+        activityId
+        createdAt
+        user {
+          id
+          name
+        }
+      }
+      ... on ActivityLikeNotification {
+        id
+        type
+        context
+        activityId
+        createdAt
+        user {
+          id
+          name
+        }
+      }
+    }
+  }
+}
+"#;
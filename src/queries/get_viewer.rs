@@ -0,0 +1,23 @@
+pub const GET_VIEWER: &str = r#"
+query {
+  Viewer {
+    id
+    name
+    about(asHtml: true)
+    avatar {
+      large
+      medium
+    }
+    bannerImage
+    unreadNotificationCount
+    siteUrl
+    options {
+      titleLanguage
+      displayAdultContent
+      airingNotifications
+      profileColor
+      timezone
+    }
+  }
+}
+"#;
@@ -0,0 +1,19 @@
+pub const SAVE_MEDIA_LIST_ENTRY: &str = r#"
+mutation ($mediaId: Int, $status: MediaListStatus, $score: Float) {
+  SaveMediaListEntry(mediaId: $mediaId, status: $status, score: $score) {
+    id
+    mediaId
+    status
+    score
+    progress
+  }
+}
+"#;
+
+pub const DELETE_MEDIA_LIST_ENTRY: &str = r#"
+mutation ($id: Int) {
+  DeleteMediaListEntry(id: $id) {
+    deleted
+  }
+}
+"#;
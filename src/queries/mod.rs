@@ -1,6 +1,103 @@
+use serde_json::Value;
+
 pub mod get_anime;
 pub mod get_character;
 pub mod get_manga;
 pub mod get_mediasids;
+pub mod get_notifications;
 pub mod get_person;
+pub mod get_viewer;
+pub mod media_list_entry;
 pub mod set_progress;
+pub mod toggle_favourite;
+
+pub fn is_mutation(query: &str) -> bool {
+    query.trim_start().starts_with("mutation")
+}
+
+/// The query with normalized whitespace followed by the variables, so that formatting changes of
+/// a query do not change the key.
+pub fn request_key(query: &str, variables: &Value) -> String {
+    let query = query.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    format!("{}\n{}", query, variables)
+}
+
+/// FNV-1a, used instead of `DefaultHasher` because file names derived from it must stay the same
+/// across Rust versions.
+pub fn hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The root fields of the operation, e.g. `Media` or `Page,Viewer`.
+pub fn operation_name(query: &str) -> String {
+    let fields: Vec<String> = root_fields(query)
+        .into_iter()
+        .map(|(_, field)| field)
+        .collect();
+    if fields.is_empty() {
+        return "query".to_owned();
+    }
+
+    fields.join(",")
+}
+
+/// The fields selected on the first root field of the operation.
+pub fn root_selection(query: &str) -> Vec<String> {
+    let Some(start) = query.find('{') else {
+        return Vec::new();
+    };
+
+    root_fields(&query[start + 1..])
+        .into_iter()
+        .map(|(_, field)| field)
+        .collect()
+}
+
+/// Every `(alias, field)` selected at the root of the operation.
+pub fn root_fields(query: &str) -> Vec<(String, String)> {
+    let Some(start) = query.find('{') else {
+        return Vec::new();
+    };
+
+    let mut fields: Vec<(String, String)> = Vec::new();
+    let mut chars = query[start + 1..].chars().peekable();
+    let mut depth: u32 = 0;
+    let mut parentheses: u32 = 0;
+    let mut token = String::new();
+    let mut alias: Option<String> = None;
+
+    while let Some(character) = chars.next() {
+        match character {
+            '(' => parentheses += 1,
+            ')' => parentheses = parentheses.saturating_sub(1),
+            _ if parentheses > 0 => {}
+            '{' => depth += 1,
+            '}' if depth == 0 => break,
+            '}' => depth -= 1,
+            _ if depth > 0 => {}
+            ':' => alias = Some(std::mem::take(&mut token)),
+            character if character.is_alphanumeric() || character == '_' => {
+                token.push(character);
+                let next_is_word = chars
+                    .peek()
+                    .is_some_and(|next| next.is_alphanumeric() || *next == '_');
+                if !next_is_word {
+                    // The token is complete, but it may still be an alias followed by `:`.
+                    while chars.peek().is_some_and(|next| next.is_whitespace()) {
+                        chars.next();
+                    }
+                    if chars.peek() != Some(&':') {
+                        let field = std::mem::take(&mut token);
+                        fields.push((alias.take().unwrap_or_else(|| field.clone()), field));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fields
+}
@@ -0,0 +1,31 @@
+pub const TOGGLE_FAVOURITE: &str = r#"
+mutation ($animeId: Int, $mangaId: Int, $characterId: Int, $staffId: Int, $studioId: Int) {
+  ToggleFavourite(animeId: $animeId, mangaId: $mangaId, characterId: $characterId, staffId: $staffId, studioId: $studioId) {
+    anime {
+      nodes {
+        id
+      }
+    }
+    manga {
+      nodes {
+        id
+      }
+    }
+    characters {
+      nodes {
+        id
+      }
+    }
+    staff {
+      nodes {
+        id
+      }
+    }
+    studios {
+      nodes {
+        id
+      }
+    }
+  }
+}
+"#;
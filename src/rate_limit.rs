@@ -0,0 +1,237 @@
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+
+const WINDOW: Duration = Duration::from_secs(60);
+/// How long the limiter waits when the server asks for longer than an `Instant` can hold.
+const MAX_WAIT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The current instant on the clock of tokio, which is the system clock unless a test paused it.
+pub(crate) fn now() -> Instant {
+    tokio::time::Instant::now().into_std()
+}
+
+/// `now() + delay`, capped instead of panicking on the `Retry-After` or `X-RateLimit-Reset` of a
+/// misbehaving server.
+fn after(delay: Duration) -> Instant {
+    now().checked_add(delay).unwrap_or_else(|| now() + MAX_WAIT)
+}
+
+/// The order in which requests get the shared rate limit budget. Requests of a priority wait
+/// while requests of a higher one are waiting, and background requests leave the last quarter of
+/// the budget of each window to the others.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Background,
+    #[default]
+    Normal,
+    /// Not paced, for requests a user is waiting on.
+    Interactive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_at: Instant,
+}
+
+impl RateLimit {
+    pub fn reset_in(&self) -> Duration {
+        self.reset_at.saturating_duration_since(now())
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    state: Mutex<State>,
+    pacing: bool,
+    notify: Notify,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    rate_limit: Option<RateLimit>,
+    /// The number of waiting requests, indexed by priority.
+    waiting: [u32; 3],
+}
+
+enum Admission {
+    Reserved(Duration),
+    Wait(Option<Duration>),
+}
+
+impl RateLimiter {
+    pub(crate) fn new(pacing: bool) -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            pacing,
+            notify: Notify::new(),
+        }
+    }
+
+    pub(crate) fn current(&self) -> Option<RateLimit> {
+        let mut state = self.state();
+        RateLimiter::roll_window(&mut state.rate_limit);
+
+        state.rate_limit
+    }
+
+    /// Waits until the budget allows one more request of `priority`, then reserves it.
+    pub(crate) async fn acquire(&self, priority: Priority) {
+        let waiting = Waiting::new(self, priority);
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            match self.admit(priority) {
+                Admission::Reserved(delay) => {
+                    drop(waiting);
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+                    return;
+                }
+                Admission::Wait(Some(timeout)) => {
+                    tokio::time::timeout(timeout, notified).await.ok();
+                }
+                Admission::Wait(None) => notified.await,
+            }
+        }
+    }
+
+    fn admit(&self, priority: Priority) -> Admission {
+        let mut state = self.state();
+        RateLimiter::roll_window(&mut state.rate_limit);
+        if state.waiting[priority as usize + 1..]
+            .iter()
+            .any(|waiting| *waiting > 0)
+        {
+            return Admission::Wait(None);
+        }
+
+        let Some(rate_limit) = state.rate_limit.as_mut() else {
+            return Admission::Reserved(Duration::ZERO);
+        };
+        let reserve = match priority {
+            Priority::Background => rate_limit.limit / 4,
+            Priority::Normal | Priority::Interactive => 0,
+        };
+        if rate_limit.remaining <= reserve {
+            return Admission::Wait(Some(rate_limit.reset_in()));
+        }
+
+        let delay = if self.pacing && priority != Priority::Interactive {
+            RateLimiter::pacing_delay(rate_limit)
+        } else {
+            Duration::ZERO
+        };
+        rate_limit.remaining -= 1;
+
+        Admission::Reserved(delay)
+    }
+
+    /// `reset` is the Unix timestamp of the end of the window, when the server sends one.
+    pub(crate) fn update(&self, limit: Option<u32>, remaining: Option<u32>, reset: Option<u64>) {
+        let (Some(limit), Some(remaining)) = (limit, remaining) else {
+            return;
+        };
+        let mut state = self.state();
+        RateLimiter::roll_window(&mut state.rate_limit);
+
+        let mut rate_limit = match state.rate_limit {
+            Some(previous) if remaining.saturating_add(1) < limit => RateLimit {
+                limit,
+                remaining: remaining.min(previous.remaining),
+                reset_at: previous.reset_at,
+            },
+            _ => RateLimit {
+                limit,
+                remaining,
+                reset_at: now() + WINDOW,
+            },
+        };
+        if let Some(reset) = reset {
+            let unix_now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            rate_limit.reset_at = after(Duration::from_secs(reset.saturating_sub(unix_now)));
+        }
+
+        state.rate_limit = Some(rate_limit);
+        drop(state);
+        self.notify.notify_waiters();
+    }
+
+    pub(crate) fn exhausted(&self, retry_after: Option<Duration>) {
+        let mut state = self.state();
+        let reset_at = after(retry_after.unwrap_or(WINDOW));
+        let limit = state
+            .rate_limit
+            .map(|rate_limit| rate_limit.limit)
+            .unwrap_or_default();
+
+        state.rate_limit = Some(RateLimit {
+            limit,
+            remaining: 0,
+            reset_at,
+        });
+        drop(state);
+        self.notify.notify_waiters();
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// A limit of `0` is unknown, from a 429 without rate limit headers, and is forgotten once its
+    /// window is over instead of blocking every later window.
+    fn roll_window(state: &mut Option<RateLimit>) {
+        match state {
+            Some(rate_limit) if rate_limit.reset_at <= now() => {
+                if rate_limit.limit == 0 {
+                    *state = None;
+                } else {
+                    rate_limit.remaining = rate_limit.limit;
+                    rate_limit.reset_at = now() + WINDOW;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Spreads the last tenth of the budget evenly over what is left of the window.
+    fn pacing_delay(rate_limit: &RateLimit) -> Duration {
+        if rate_limit.remaining > rate_limit.limit / 10 {
+            return Duration::ZERO;
+        }
+
+        rate_limit.reset_in() / rate_limit.remaining.max(1)
+    }
+}
+
+/// Counts a request as waiting until it is dropped, including when its future is cancelled.
+struct Waiting<'a> {
+    rate_limiter: &'a RateLimiter,
+    priority: Priority,
+}
+
+impl<'a> Waiting<'a> {
+    fn new(rate_limiter: &'a RateLimiter, priority: Priority) -> Self {
+        rate_limiter.state().waiting[priority as usize] += 1;
+
+        Self {
+            rate_limiter,
+            priority,
+        }
+    }
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.rate_limiter.state().waiting[self.priority as usize] -= 1;
+        self.rate_limiter.notify.notify_waiters();
+    }
+}
@@ -0,0 +1,59 @@
+use crate::errors::{Error, GraphQlError, PathSegment};
+
+/// The `data` of a GraphQL response together with its `errors`. AniList may return both, for
+/// example when a nested field is private or points to a deleted entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response<T> {
+    pub data: Option<T>,
+    pub errors: Vec<GraphQlError>,
+}
+
+impl<T> Response<T> {
+    pub(crate) fn from_data(data: T) -> Self {
+        Self {
+            data: Some(data),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.data.is_some() && !self.errors.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// The errors located at `path` or below, e.g. `Media.relations.edges.3`.
+    pub fn errors_at<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a GraphQlError> {
+        let expected: Vec<&str> = path.split('.').filter(|part| !part.is_empty()).collect();
+
+        self.errors.iter().filter(move |error| {
+            error.path.len() >= expected.len()
+                && error
+                    .path
+                    .iter()
+                    .zip(&expected)
+                    .all(|(segment, expected)| match segment {
+                        PathSegment::Field(field) => field == expected,
+                        PathSegment::Index(index) => index.to_string() == *expected,
+                    })
+        })
+    }
+
+    /// Fails when there is any error, even if some data was returned.
+    pub fn into_result(self) -> Result<T, Error> {
+        if !self.errors.is_empty() {
+            return Err(Error::GraphQl(self.errors));
+        }
+
+        self.data.ok_or(Error::NotFound)
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Response<U> {
+        Response {
+            data: self.data.map(f),
+            errors: self.errors,
+        }
+    }
+}
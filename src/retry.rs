@@ -0,0 +1,160 @@
+use crate::errors::Error;
+use crate::rate_limit::now;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_mutations: bool,
+    circuit_breaker_threshold: u32,
+    circuit_breaker_cooldown: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_mutations: false,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+
+        self
+    }
+
+    pub fn with_backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay.max(base_delay);
+
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// Mutations are not idempotent, so they are only retried when this is enabled.
+    pub fn with_retry_mutations(mut self, retry_mutations: bool) -> Self {
+        self.retry_mutations = retry_mutations;
+
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, threshold: u32, cooldown: Duration) -> Self {
+        self.circuit_breaker_threshold = threshold;
+        self.circuit_breaker_cooldown = cooldown;
+
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub(crate) fn should_retry(&self, error: &Error, attempt: u32, mutation: bool) -> bool {
+        if attempt >= self.max_attempts || (mutation && !self.retry_mutations) {
+            return false;
+        }
+
+        match error {
+            Error::Transport(_) | Error::Timeout => true,
+            Error::Http { status, .. } => RetryPolicy::is_transient(*status),
+            Error::GraphQl(errors) => errors
+                .iter()
+                .any(|error| error.status.is_some_and(RetryPolicy::is_transient)),
+            _ => false,
+        }
+    }
+
+    /// Exponential backoff; with jitter the delay is drawn uniformly from zero to that bound.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+
+        let random = RandomState::new().build_hasher().finish();
+        delay.mul_f64(random as f64 / u64::MAX as f64)
+    }
+
+    fn is_transient(status: u16) -> bool {
+        matches!(status, 500 | 502 | 503 | 504)
+    }
+}
+
+/// Stops sending requests for a while once AniList keeps answering 503, which it does during
+/// maintenance.
+#[derive(Debug, Default)]
+pub(crate) struct CircuitBreaker {
+    state: Mutex<CircuitState>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        let state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        match state.open_until {
+            Some(open_until) if open_until > now() => Err(Error::Unavailable {
+                retry_after: open_until.saturating_duration_since(now()),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn record<T>(&self, result: &Result<T, Error>, policy: &RetryPolicy) {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        match result {
+            Err(error) if error.status() == Some(503) => {
+                state.consecutive_failures += 1;
+                if policy.circuit_breaker_threshold > 0
+                    && state.consecutive_failures >= policy.circuit_breaker_threshold
+                {
+                    state.open_until = Some(now() + policy.circuit_breaker_cooldown);
+                    state.consecutive_failures = 0;
+                }
+            }
+            Err(Error::Transport(_) | Error::Timeout) => {}
+            _ => {
+                state.consecutive_failures = 0;
+                state.open_until = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use crate::errors::Error;
+use crate::models::Status;
+use crate::rate_limit::Priority;
+use crate::response::Response;
+use crate::token_store::{StoredToken, TokenStore};
+use crate::AniListClient;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The operations of one AniList user. Sessions are cheap, any number of them can share the
+/// HTTP transport and rate limit of the client they were created from.
+#[derive(Clone)]
+pub struct Session {
+    client: AniListClient,
+    token: TokenSource,
+}
+
+#[derive(Clone)]
+enum TokenSource {
+    Static(Arc<str>),
+    Store {
+        store: Arc<dyn TokenStore>,
+        user_id: i64,
+        expiry_warning: Option<Arc<ExpiryWarning>>,
+    },
+}
+
+struct ExpiryWarning {
+    before: Duration,
+    callback: Box<dyn Fn(&StoredToken) + Send + Sync>,
+    warned: AtomicBool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FavouriteTarget {
+    Anime(i64),
+    Manga(i64),
+    Character(i64),
+    Staff(i64),
+    Studio(i64),
+}
+
+impl AniListClient {
+    pub fn session(&self, access_token: impl Into<String>) -> Session {
+        Session {
+            client: self.clone(),
+            token: TokenSource::Static(Arc::from(access_token.into())),
+        }
+    }
+
+    /// A session whose token is read from `store` when it is needed, so that tokens replaced in
+    /// the store are picked up.
+    pub fn session_from_store(&self, store: impl TokenStore + 'static, user_id: i64) -> Session {
+        Session {
+            client: self.clone(),
+            token: TokenSource::Store {
+                store: Arc::new(store),
+                user_id,
+                expiry_warning: None,
+            },
+        }
+    }
+
+    /// The session of the token given to [`crate::AniListClientBuilder::with_anilist_token`].
+    pub fn viewer_session(&self) -> Result<Session, Error> {
+        self.anilist_token()
+            .map(|access_token| self.session(access_token))
+            .ok_or(Error::MissingToken)
+    }
+}
+
+impl Session {
+    pub fn client(&self) -> &AniListClient {
+        &self.client
+    }
+
+    /// See [`AniListClient::with_priority`].
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.client = self.client.with_priority(priority);
+
+        self
+    }
+
+    /// Calls `callback` once when the stored token is found to expire within `before`. Only
+    /// applies to sessions created with [`AniListClient::session_from_store`].
+    pub fn with_expiry_warning(
+        mut self,
+        before: Duration,
+        callback: impl Fn(&StoredToken) + Send + Sync + 'static,
+    ) -> Self {
+        if let TokenSource::Store { expiry_warning, .. } = &mut self.token {
+            *expiry_warning = Some(Arc::new(ExpiryWarning {
+                before,
+                callback: Box::new(callback),
+                warned: AtomicBool::new(false),
+            }));
+        }
+
+        self
+    }
+
+    pub async fn access_token(&self) -> Result<Arc<str>, Error> {
+        let (store, user_id, expiry_warning) = match &self.token {
+            TokenSource::Static(access_token) => return Ok(Arc::clone(access_token)),
+            TokenSource::Store {
+                store,
+                user_id,
+                expiry_warning,
+            } => (store, *user_id, expiry_warning),
+        };
+
+        let token = store.load(user_id).await?.ok_or(Error::MissingToken)?;
+        if token.is_expired() {
+            return Err(Error::TokenExpired { user_id });
+        }
+        if let Some(expiry_warning) = expiry_warning {
+            if token.expires_within(expiry_warning.before)
+                && !expiry_warning.warned.swap(true, Ordering::Relaxed)
+            {
+                (expiry_warning.callback)(&token);
+            }
+        }
+
+        Ok(Arc::from(token.access_token))
+    }
+
+    pub async fn viewer(&self) -> Result<Value, Error> {
+        self.request("viewer", "get", json!({})).await
+    }
+
+    pub async fn notifications(
+        &self,
+        page: i64,
+        per_page: i64,
+        reset_count: bool,
+    ) -> Result<Value, Error> {
+        self.request(
+            "notifications",
+            "get",
+            json!({"page": page, "per_page": per_page, "reset": reset_count}),
+        )
+        .await
+    }
+
+    pub async fn set_progress(&self, new_chapter: i64, media_id: i64) -> Result<Value, Error> {
+        self.request(
+            "progress",
+            "set",
+            json!({"progress": new_chapter, "mediaId": media_id, "hidden": true}),
+        )
+        .await
+    }
+
+    pub async fn set_increment_progress(
+        &self,
+        progress_start: i64,
+        progress_end: i64,
+        media_id: i64,
+    ) -> Result<Value, Error> {
+        self.request(
+            "progress_increment",
+            "set",
+            json!({"progress_start": progress_start, "progress_end": progress_end, "mediaId": media_id, "hidden": true}),
+        )
+        .await
+    }
+
+    pub async fn toggle_favourite(&self, target: FavouriteTarget) -> Result<Value, Error> {
+        let variables = match target {
+            FavouriteTarget::Anime(id) => json!({ "animeId": id }),
+            FavouriteTarget::Manga(id) => json!({ "mangaId": id }),
+            FavouriteTarget::Character(id) => json!({ "characterId": id }),
+            FavouriteTarget::Staff(id) => json!({ "staffId": id }),
+            FavouriteTarget::Studio(id) => json!({ "studioId": id }),
+        };
+
+        self.request("favourite", "set", variables).await
+    }
+
+    /// Adds the media to the list of the user or updates its entry. `None` leaves the status or
+    /// score of an existing entry unchanged, the score is in the scoring format of the user.
+    pub async fn save_list_entry(
+        &self,
+        media_id: i64,
+        status: Option<Status>,
+        score: Option<f64>,
+    ) -> Result<Value, Error> {
+        let mut variables = json!({ "mediaId": media_id });
+        if let Some(status) = status {
+            variables["status"] = json!(status);
+        }
+        if let Some(score) = score {
+            variables["score"] = json!(score);
+        }
+
+        self.request("list_entry", "set", variables).await
+    }
+
+    /// `entry_id` is the `id` of the list entry, as returned by
+    /// [`Session::save_list_entry`], not the id of its media.
+    pub async fn delete_list_entry(&self, entry_id: i64) -> Result<Value, Error> {
+        self.request("list_entry", "delete", json!({ "id": entry_id }))
+            .await
+    }
+
+    /// Like [`AniListClient::execute`], authenticated as this session.
+    pub async fn execute<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, Error> {
+        self.execute_partial(query, variables).await?.into_result()
+    }
+
+    pub async fn execute_partial<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<Response<T>, Error> {
+        let access_token = self.access_token().await?;
+        self.client
+            .execute_with_token(query, variables, Some(&access_token))
+            .await
+    }
+
+    async fn request(
+        &self,
+        media_type: &str,
+        action: &str,
+        variables: Value,
+    ) -> Result<Value, Error> {
+        let access_token = self.access_token().await?;
+        self.client
+            .request(media_type, action, variables, Some(&access_token))
+            .await?
+            .into_result()
+            .map(|data| json!({ "data": data }))
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session").finish_non_exhaustive()
+    }
+}
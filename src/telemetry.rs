@@ -0,0 +1,53 @@
+use crate::errors::Error;
+use crate::metrics::RequestRecord;
+use serde_json::Value;
+use tracing::Span;
+
+/// The variables with the values of the fields that may hold secrets replaced.
+pub(crate) fn redact(variables: &Value) -> Value {
+    match variables {
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(field, value)| {
+                    let value = if is_secret(field) {
+                        Value::String("[redacted]".to_owned())
+                    } else {
+                        redact(value)
+                    };
+
+                    (field.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(redact).collect()),
+        value => value.clone(),
+    }
+}
+
+pub(crate) fn record(span: &Span, record: &RequestRecord, error: Option<&Error>) {
+    span.record("success", record.success);
+    if let Some(status) = record.status {
+        span.record("status", status);
+    }
+    span.record("retries", record.retries);
+    span.record("cache_hit", record.cache_hit);
+    if let Some(remaining) = record.rate_limit_remaining {
+        span.record("rate_limit_remaining", remaining);
+    }
+    span.record("duration_ms", record.duration.as_millis() as u64);
+
+    match error {
+        Some(error) => tracing::warn!(parent: span, error = %error, "AniList request failed"),
+        None => tracing::debug!(parent: span, "AniList request completed"),
+    }
+}
+
+fn is_secret(field: &str) -> bool {
+    let field = field.to_ascii_lowercase();
+
+    field == "code"
+        || ["token", "secret", "password"]
+            .iter()
+            .any(|secret| field.contains(secret))
+}
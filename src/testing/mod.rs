@@ -0,0 +1,3 @@
+mod server;
+
+pub use self::server::{MockServer, ReceivedRequest};
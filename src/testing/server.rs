@@ -0,0 +1,364 @@
+use crate::queries;
+use serde_json::{json, Map, Value};
+use std::io;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// A local stand-in for `graphql.anilist.co` that answers from fixtures, keyed by the root field
+/// of the query (`Media`, `Character`, `Staff`, `SaveMediaListEntry`...).
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<ServerState>>,
+    handle: JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedRequest {
+    pub operation: Option<String>,
+    pub query: String,
+    pub variables: Value,
+    pub headers: Vec<(String, String)>,
+}
+
+impl ReceivedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Default)]
+struct ServerState {
+    fixtures: Vec<Fixture>,
+    failures: Vec<(u16, Value)>,
+    rate_limit: Option<RateLimitState>,
+    requests: Vec<ReceivedRequest>,
+}
+
+struct Fixture {
+    operation: String,
+    variables: Option<Value>,
+    data: Value,
+}
+
+struct RateLimitState {
+    limit: u32,
+    remaining: u32,
+    window: Duration,
+    reset_at: SystemTime,
+}
+
+impl RateLimitState {
+    fn reset_timestamp(&self) -> u64 {
+        self.reset_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
+impl MockServer {
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(ServerState::default()));
+
+        let server_state = Arc::clone(&state);
+        let handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = Arc::clone(&server_state);
+                tokio::spawn(async move {
+                    let _ = MockServer::handle(stream, state).await;
+                });
+            }
+        });
+
+        Ok(Self {
+            address,
+            state,
+            handle,
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.address)
+    }
+
+    /// Answers every query on `operation` with `data`.
+    pub fn fixture(&self, operation: impl Into<String>, data: Value) {
+        self.push_fixture(Fixture {
+            operation: operation.into(),
+            variables: None,
+            data,
+        });
+    }
+
+    /// Answers queries on `operation` whose variables contain `variables` with `data`.
+    pub fn fixture_for(&self, operation: impl Into<String>, variables: Value, data: Value) {
+        self.push_fixture(Fixture {
+            operation: operation.into(),
+            variables: Some(variables),
+            data,
+        });
+    }
+
+    /// Loads every `.json` file of `directory`. A file either holds the data of the operation
+    /// named after it (`Media.json`), or an object with `operation`, `data` and optionally
+    /// `variables` keys.
+    pub async fn load_fixtures(&self, directory: impl AsRef<Path>) -> io::Result<()> {
+        let mut entries = tokio::fs::read_dir(directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+
+            let content = tokio::fs::read_to_string(&path).await?;
+            let value: Value = serde_json::from_str(&content)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            match value.get("operation").and_then(Value::as_str) {
+                Some(operation) => self.push_fixture(Fixture {
+                    operation: operation.to_owned(),
+                    variables: value.get("variables").cloned(),
+                    data: value.get("data").cloned().unwrap_or_default(),
+                }),
+                None => {
+                    let operation = path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .unwrap_or_default();
+                    self.fixture(operation, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Makes the next request fail with `status` and `body` instead of being answered.
+    pub fn fail_next(&self, status: u16, body: Value) {
+        self.lock().failures.push((status, body));
+    }
+
+    /// Sends AniList rate limit headers and answers 429 once `limit` requests were made in the
+    /// current `window`.
+    pub fn with_rate_limit(&self, limit: u32, window: Duration) {
+        self.lock().rate_limit = Some(RateLimitState {
+            limit,
+            remaining: limit,
+            window,
+            reset_at: SystemTime::now() + window,
+        });
+    }
+
+    pub fn reset_rate_limit(&self) {
+        if let Some(rate_limit) = self.lock().rate_limit.as_mut() {
+            rate_limit.remaining = rate_limit.limit;
+            rate_limit.reset_at = SystemTime::now() + rate_limit.window;
+        }
+    }
+
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.lock().requests.clone()
+    }
+
+    fn push_fixture(&self, fixture: Fixture) {
+        self.lock().fixtures.push(fixture);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ServerState> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    async fn handle(mut stream: TcpStream, state: Arc<Mutex<ServerState>>) -> io::Result<()> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let header_end = loop {
+            let mut chunk = [0; 4096];
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+            if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break position + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+        let headers: Vec<(String, String)> = head
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+            .collect();
+        let content_length = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+            .unwrap_or_default();
+        while buffer.len() < header_end + content_length {
+            let mut chunk = [0; 4096];
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+
+        let body: Value = serde_json::from_slice(&buffer[header_end..]).unwrap_or_default();
+        let (status, extra_headers, body) = {
+            let mut state = state.lock().unwrap_or_else(|error| error.into_inner());
+            MockServer::respond(&mut state, headers, body)
+        };
+
+        let body = body.to_string();
+        let mut response = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            status,
+            MockServer::reason(status),
+            body.len()
+        );
+        for (name, value) in extra_headers {
+            response.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        response.push_str("\r\n");
+        response.push_str(&body);
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    fn respond(
+        state: &mut ServerState,
+        headers: Vec<(String, String)>,
+        body: Value,
+    ) -> (u16, Vec<(String, String)>, Value) {
+        let query = body["query"].as_str().unwrap_or_default().to_owned();
+        let variables = body.get("variables").cloned().unwrap_or(Value::Null);
+        let fields = queries::root_fields(&query);
+        state.requests.push(ReceivedRequest {
+            operation: fields.first().map(|(_, field)| field.clone()),
+            query,
+            variables: variables.clone(),
+            headers,
+        });
+
+        let mut extra_headers: Vec<(String, String)> = Vec::new();
+        if let Some(rate_limit) = state.rate_limit.as_mut() {
+            if rate_limit.reset_at <= SystemTime::now() {
+                rate_limit.remaining = rate_limit.limit;
+                rate_limit.reset_at = SystemTime::now() + rate_limit.window;
+            }
+            if rate_limit.remaining == 0 {
+                let retry_after = rate_limit
+                    .reset_at
+                    .duration_since(SystemTime::now())
+                    .unwrap_or_default();
+                return (
+                    429,
+                    vec![
+                        ("X-RateLimit-Limit".to_owned(), rate_limit.limit.to_string()),
+                        ("X-RateLimit-Remaining".to_owned(), "0".to_owned()),
+                        (
+                            "X-RateLimit-Reset".to_owned(),
+                            rate_limit.reset_timestamp().to_string(),
+                        ),
+                        (
+                            "Retry-After".to_owned(),
+                            retry_after.as_secs_f64().ceil().to_string(),
+                        ),
+                    ],
+                    json!({"errors": [{"message": "Too Many Requests.", "status": 429}], "data": null}),
+                );
+            }
+
+            rate_limit.remaining -= 1;
+            extra_headers.push(("X-RateLimit-Limit".to_owned(), rate_limit.limit.to_string()));
+            extra_headers.push((
+                "X-RateLimit-Remaining".to_owned(),
+                rate_limit.remaining.to_string(),
+            ));
+            extra_headers.push((
+                "X-RateLimit-Reset".to_owned(),
+                rate_limit.reset_timestamp().to_string(),
+            ));
+        }
+
+        if !state.failures.is_empty() {
+            let (status, body) = state.failures.remove(0);
+            return (status, extra_headers, body);
+        }
+
+        let mut data = Map::new();
+        let mut missing = false;
+        for (alias, field) in &fields {
+            let fixture = state
+                .fixtures
+                .iter()
+                .rev()
+                .filter(|fixture| &fixture.operation == field)
+                .find(|fixture| match &fixture.variables {
+                    Some(expected) => contains(&variables, expected),
+                    None => true,
+                });
+            match fixture {
+                Some(fixture) => {
+                    data.insert(alias.clone(), fixture.data.clone());
+                }
+                None => {
+                    missing = true;
+                    data.insert(alias.clone(), Value::Null);
+                }
+            }
+        }
+
+        if missing {
+            (
+                404,
+                extra_headers,
+                json!({"errors": [{"message": "Not Found.", "status": 404}], "data": data}),
+            )
+        } else {
+            (200, extra_headers, json!({ "data": data }))
+        }
+    }
+
+    fn reason(status: u16) -> &'static str {
+        match status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            429 => "Too Many Requests",
+            500 => "Internal Server Error",
+            502 => "Bad Gateway",
+            503 => "Service Unavailable",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+fn contains(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::Object(value), Value::Object(expected)) => {
+            expected.iter().all(|(key, expected)| {
+                value
+                    .get(key)
+                    .is_some_and(|value| contains(value, expected))
+            })
+        }
+        _ => value == expected,
+    }
+}
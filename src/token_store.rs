@@ -0,0 +1,195 @@
+use crate::errors::Error;
+use crate::oauth;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredToken {
+    pub user_id: i64,
+    pub access_token: String,
+    pub expires_at: Option<SystemTime>,
+}
+
+impl StoredToken {
+    pub fn new(
+        user_id: i64,
+        access_token: impl Into<String>,
+        expires_at: Option<SystemTime>,
+    ) -> Self {
+        Self {
+            user_id,
+            access_token: access_token.into(),
+            expires_at,
+        }
+    }
+
+    /// Reads the user id and expiry from the token itself.
+    pub fn from_access_token(access_token: impl Into<String>) -> Result<Self, Error> {
+        let access_token = access_token.into();
+        let claims = oauth::decode_token(&access_token)?;
+
+        Ok(Self {
+            user_id: claims.user_id,
+            access_token,
+            expires_at: Some(claims.expires_at),
+        })
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= SystemTime::now())
+    }
+
+    pub fn expires_within(&self, duration: Duration) -> bool {
+        self.expires_at.is_some_and(|expires_at| {
+            SystemTime::now()
+                .checked_add(duration)
+                .is_none_or(|limit| expires_at <= limit)
+        })
+    }
+}
+
+#[async_trait]
+pub trait TokenStore: Send + Sync {
+    async fn load(&self, user_id: i64) -> Result<Option<StoredToken>, Error>;
+
+    async fn save(&self, token: &StoredToken) -> Result<(), Error>;
+
+    async fn remove(&self, user_id: i64) -> Result<(), Error>;
+
+    async fn list(&self) -> Result<Vec<StoredToken>, Error>;
+
+    /// The tokens that are expired or will be within `duration`, to re-authenticate their users
+    /// in time.
+    async fn expiring_within(&self, duration: Duration) -> Result<Vec<StoredToken>, Error> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|token| token.expires_within(duration))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<T: TokenStore + ?Sized> TokenStore for Arc<T> {
+    async fn load(&self, user_id: i64) -> Result<Option<StoredToken>, Error> {
+        (**self).load(user_id).await
+    }
+
+    async fn save(&self, token: &StoredToken) -> Result<(), Error> {
+        (**self).save(token).await
+    }
+
+    async fn remove(&self, user_id: i64) -> Result<(), Error> {
+        (**self).remove(user_id).await
+    }
+
+    async fn list(&self) -> Result<Vec<StoredToken>, Error> {
+        (**self).list().await
+    }
+}
+
+/// Keeps one `<user id>.json` file per user in `directory`.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    directory: PathBuf,
+}
+
+impl FileTokenStore {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, user_id: i64) -> PathBuf {
+        self.directory.join(format!("{}.json", user_id))
+    }
+
+    /// Writes to a temporary file only readable by its owner, then renames it, so that the token
+    /// is never readable by other users, even briefly, nor left truncated by a crash.
+    fn write(path: &Path, content: &[u8]) -> std::io::Result<()> {
+        let temporary_path = path.with_extension("json.tmp");
+        match std::fs::remove_file(&temporary_path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error),
+            _ => {}
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&temporary_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        drop(file);
+
+        std::fs::rename(&temporary_path, path)
+    }
+}
+
+#[async_trait]
+impl TokenStore for FileTokenStore {
+    async fn load(&self, user_id: i64) -> Result<Option<StoredToken>, Error> {
+        let path = self.path(user_id);
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|error| Error::decode(path.display().to_string(), error))
+    }
+
+    async fn save(&self, token: &StoredToken) -> Result<(), Error> {
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let content =
+            serde_json::to_string_pretty(token).map_err(|error| Error::decode("", error))?;
+        let path = self.path(token.user_id);
+
+        tokio::task::spawn_blocking(move || FileTokenStore::write(&path, content.as_bytes()))
+            .await
+            .map_err(std::io::Error::other)??;
+
+        Ok(())
+    }
+
+    async fn remove(&self, user_id: i64) -> Result<(), Error> {
+        match tokio::fs::remove_file(self.path(user_id)).await {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<StoredToken>, Error> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut tokens: Vec<StoredToken> = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let user_id = entry
+                .path()
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<i64>().ok());
+            if let Some(token) = match user_id {
+                Some(user_id) => self.load(user_id).await?,
+                None => None,
+            } {
+                tokens.push(token);
+            }
+        }
+
+        Ok(tokens)
+    }
+}
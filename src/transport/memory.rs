@@ -0,0 +1,64 @@
+use crate::errors::Error;
+use crate::transport::{Transport, TransportRequest, TransportResponse};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::error::Error as StdError;
+use std::sync::{Arc, Mutex};
+
+/// Serves queued responses in order and keeps every request it received.
+#[derive(Debug, Default)]
+pub struct MemoryTransport {
+    responses: Mutex<VecDeque<TransportResponse>>,
+    requests: Mutex<Vec<TransportRequest>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_response(&self, response: TransportResponse) {
+        self.responses
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push_back(response);
+    }
+
+    pub fn push_json(&self, status: u16, body: serde_json::Value) {
+        self.push_response(TransportResponse::json(status, &body));
+    }
+
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+
+    pub fn pending_responses(&self) -> usize {
+        self.responses
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .len()
+    }
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push(request);
+
+        self.responses
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .pop_front()
+            .ok_or_else(|| {
+                let error: Box<dyn StdError + Send + Sync> =
+                    "The memory transport has no response queued.".into();
+                Error::Transport(Arc::from(error))
+            })
+    }
+}
@@ -0,0 +1,144 @@
+use crate::errors::Error;
+use crate::queries;
+use crate::transport::{Transport, TransportRequest, TransportResponse};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error as StdError;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Forwards requests to another transport and writes every GraphQL exchange to `directory`, one
+/// JSON file per query and variables. Request headers and other requests, e.g. OAuth token
+/// exchanges, are not written, so tokens never end up on disk.
+pub struct RecordingTransport<T> {
+    inner: T,
+    directory: PathBuf,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T, directory: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            directory: directory.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for RecordingTransport<T> {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let Some(recorded_request) = RecordedRequest::from_body(&request.body) else {
+            return self.inner.send(request).await;
+        };
+        let response = self.inner.send(request).await?;
+
+        let recording = Recording {
+            request: recorded_request,
+            response: RecordedResponse {
+                status: response.status,
+                headers: response.headers.clone(),
+                body: serde_json::from_str(&response.body)
+                    .unwrap_or_else(|_| Value::String(response.body.clone())),
+            },
+        };
+        let content =
+            serde_json::to_string_pretty(&recording).map_err(|error| Error::decode("", error))?;
+        tokio::fs::create_dir_all(&self.directory).await?;
+        tokio::fs::write(self.directory.join(recording.request.file_name()), content).await?;
+
+        Ok(response)
+    }
+}
+
+/// Serves the exchanges written by a [`RecordingTransport`] without touching the network.
+pub struct ReplayTransport {
+    directory: PathBuf,
+}
+
+impl ReplayTransport {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let Some(recorded_request) = RecordedRequest::from_body(&request.body) else {
+            let error: Box<dyn StdError + Send + Sync> =
+                "Only GraphQL requests can be replayed".into();
+            return Err(Error::Transport(Arc::from(error)));
+        };
+        let file_name = recorded_request.file_name();
+        let content = match tokio::fs::read_to_string(self.directory.join(&file_name)).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                let error: Box<dyn StdError + Send + Sync> =
+                    format!("No recording found for this request: {}", file_name).into();
+                return Err(Error::Transport(Arc::from(error)));
+            }
+            Err(error) => return Err(error.into()),
+        };
+        let recording: Recording = serde_json::from_str(&content)
+            .map_err(|error| Error::decode(file_name.clone(), error))?;
+
+        Ok(TransportResponse {
+            status: recording.response.status,
+            headers: recording.response.headers,
+            body: match recording.response.body {
+                Value::String(body) => body,
+                body => body.to_string(),
+            },
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Recording {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedRequest {
+    query: String,
+    variables: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Value,
+}
+
+impl RecordedRequest {
+    /// `None` for requests that are not GraphQL queries.
+    fn from_body(body: &str) -> Option<Self> {
+        let body: Value = serde_json::from_str(body).ok()?;
+
+        Some(Self {
+            query: body["query"].as_str()?.to_owned(),
+            variables: body.get("variables").cloned().unwrap_or(Value::Null),
+        })
+    }
+
+    /// `<operation>-<hash>.json`, where the hash covers [`queries::request_key`].
+    fn file_name(&self) -> String {
+        let operation = queries::root_fields(&self.query)
+            .into_iter()
+            .map(|(_, field)| field)
+            .next()
+            .unwrap_or_else(|| "query".to_owned());
+        let key = queries::request_key(&self.query, &self.variables);
+
+        format!("{}-{:016x}.json", operation, queries::hash(&key))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::errors::Error;
+use crate::transport::{Transport, TransportRequest, TransportResponse};
+use async_trait::async_trait;
+
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    reqwest_client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(reqwest_client: reqwest::Client) -> Self {
+        Self { reqwest_client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let mut builder = self
+            .reqwest_client
+            .post(&request.url)
+            .timeout(request.timeout)
+            .body(request.body);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.as_str().to_owned(), value.to_owned()))
+            })
+            .collect();
+        let body = response.text().await?;
+
+        Ok(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "blocking")]
+
+use anilist::models::Status;
+use anilist::transport::MemoryTransport;
+use anilist::AniListClientBuilder;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[test]
+fn blocking_clients_and_sessions_send_their_requests() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, json!({"data": {"Media": {"id": 1, "episodes": 26}}}));
+    transport.push_json(
+        200,
+        json!({"data": {"SaveMediaListEntry": {"id": 7, "mediaId": 1, "status": "CURRENT"}}}),
+    );
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .build_blocking()
+        .unwrap();
+
+    let anime = client.get_anime(1).unwrap();
+    assert_eq!((anime.id, anime.episodes), (1, Some(26)));
+
+    let session = client.session("token");
+    let entry = session
+        .save_list_entry(1, Some(Status::Current), None)
+        .unwrap();
+    assert_eq!(entry["data"]["SaveMediaListEntry"]["id"], 7);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("Authorization"), None);
+    assert_eq!(requests[1].header("Authorization"), Some("Bearer token"));
+    let body: Value = serde_json::from_str(&requests[1].body).unwrap();
+    assert_eq!(
+        body["variables"],
+        json!({"mediaId": 1, "status": "CURRENT"})
+    );
+}
+
+#[test]
+fn blocking_errors_reach_the_caller() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(
+        404,
+        json!({"errors": [{"message": "Not Found.", "status": 404}], "data": {"Media": null}}),
+    );
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .build_blocking()
+        .unwrap();
+
+    assert_eq!(client.get_anime(2).unwrap_err().status(), Some(404));
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "client")]
+
+use anilist::transport::{MemoryTransport, TransportResponse};
+use anilist::{AniListClientBuilder, CachePolicy, RetryPolicy};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+const TTL: Duration = Duration::from_millis(50);
+
+fn client(transport: &Arc<MemoryTransport>, cache_policy: CachePolicy) -> anilist::AniListClient {
+    AniListClientBuilder::builder()
+        .with_transport(Arc::clone(transport))
+        .with_retry_policy(RetryPolicy::disabled())
+        .with_cache(cache_policy.with_operation_ttl("Media", TTL))
+        .build()
+        .unwrap()
+}
+
+fn media(episodes: i64) -> Value {
+    json!({"data": {"Media": {"id": 1, "episodes": episodes}}})
+}
+
+async fn expire() {
+    tokio::time::sleep(TTL * 2).await;
+}
+
+#[tokio::test]
+async fn fresh_entries_are_served_from_the_cache() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, media(12));
+    let client = client(&transport, CachePolicy::new());
+
+    assert_eq!(client.get_anime(1).await.unwrap().episodes, Some(12));
+    assert_eq!(client.get_anime(1).await.unwrap().episodes, Some(12));
+    assert_eq!(transport.requests().len(), 1);
+
+    expire().await;
+    transport.push_json(200, media(13));
+    assert_eq!(client.get_anime(1).await.unwrap().episodes, Some(13));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn stale_entries_are_served_while_they_are_revalidated() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, media(12));
+    let client = client(
+        &transport,
+        CachePolicy::new().with_stale_while_revalidate(true),
+    );
+    client.get_anime(1).await.unwrap();
+
+    expire().await;
+    transport.push_json(200, media(13));
+    assert_eq!(client.get_anime(1).await.unwrap().episodes, Some(12));
+    while transport.pending_responses() > 0 {
+        tokio::task::yield_now().await;
+    }
+    tokio::task::yield_now().await;
+
+    assert_eq!(client.get_anime(1).await.unwrap().episodes, Some(13));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn stale_entries_replace_server_errors_with_offline_fallback() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, media(12));
+    let client = client(&transport, CachePolicy::new().with_offline_fallback(true));
+    client.get_anime(1).await.unwrap();
+
+    expire().await;
+    transport.push_response(TransportResponse::new(503, "Service Unavailable"));
+    assert_eq!(client.get_anime(1).await.unwrap().episodes, Some(12));
+
+    // The memory transport fails like a network error once it has no response left.
+    assert_eq!(client.get_anime(1).await.unwrap().episodes, Some(12));
+
+    transport.push_json(
+        404,
+        json!({"errors": [{"message": "Not Found.", "status": 404}]}),
+    );
+    assert!(client.get_anime(1).await.is_err());
+    assert_eq!(transport.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn stale_entries_are_not_served_without_offline_fallback() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, media(12));
+    let client = client(&transport, CachePolicy::new());
+    client.get_anime(1).await.unwrap();
+
+    expire().await;
+    transport.push_response(TransportResponse::new(503, "Service Unavailable"));
+    assert_eq!(client.get_anime(1).await.unwrap_err().status(), Some(503));
+}
+
+#[tokio::test]
+async fn entries_older_than_max_stale_are_not_served() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, media(12));
+    let client = client(
+        &transport,
+        CachePolicy::new()
+            .with_offline_fallback(true)
+            .with_max_stale(Duration::ZERO),
+    );
+    client.get_anime(1).await.unwrap();
+
+    expire().await;
+    transport.push_response(TransportResponse::new(503, "Service Unavailable"));
+    assert_eq!(client.get_anime(1).await.unwrap_err().status(), Some(503));
+}
+
+#[tokio::test]
+async fn sessions_never_share_an_entry() {
+    const QUERY: &str = "query ($id: Int) { Media (id: $id) { id episodes } }";
+    let transport = Arc::new(MemoryTransport::new());
+    for episodes in [1, 2, 3] {
+        transport.push_json(200, media(episodes));
+    }
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .with_cache(CachePolicy::new())
+        .build()
+        .unwrap();
+    let first = client.session("first token");
+    let second = client.session("second token");
+    let variables = json!({"id": 1});
+
+    let data: Value = first.execute(QUERY, variables.clone()).await.unwrap();
+    assert_eq!(data["Media"]["episodes"], 1);
+    let data: Value = second.execute(QUERY, variables.clone()).await.unwrap();
+    assert_eq!(data["Media"]["episodes"], 2);
+    let data: Value = client.execute(QUERY, variables.clone()).await.unwrap();
+    assert_eq!(data["Media"]["episodes"], 3);
+    assert_eq!(transport.requests().len(), 3);
+
+    let data: Value = first.execute(QUERY, variables.clone()).await.unwrap();
+    assert_eq!(data["Media"]["episodes"], 1);
+    let data: Value = second.execute(QUERY, variables.clone()).await.unwrap();
+    assert_eq!(data["Media"]["episodes"], 2);
+    let data: Value = client.execute(QUERY, variables).await.unwrap();
+    assert_eq!(data["Media"]["episodes"], 3);
+    assert_eq!(transport.requests().len(), 3);
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "client")]
+
+use anilist::entity_store::{EntityKind, EntityStore};
+use anilist::transport::MemoryTransport;
+use anilist::AniListClientBuilder;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const ANIME: &str = include_str!("fixtures/anime.json");
+
+const SEARCH: &str = "query ($id: Int) { Media (id: $id) { id type title { romaji english } } }";
+
+fn partial() -> Value {
+    json!({"Media": {
+        "id": 1,
+        "type": "ANIME",
+        "title": {"romaji": "Cowboy Bebop", "english": null},
+        "bannerImage": null,
+        "characters": {"edges": [
+            {"id": 900, "role": "MAIN", "node": {"id": 1, "name": {"full": "Spike Spiegel"}}},
+        ]},
+        "relations": {"edges": [
+            {"id": 28, "relationType": "ADAPTATION", "node": {"id": 30, "type": "MANGA"}},
+        ]},
+    }})
+}
+
+#[tokio::test]
+async fn partial_and_full_responses_are_merged() {
+    let mut full: Value = serde_json::from_str(ANIME).unwrap();
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, json!({"data": partial()}));
+    transport.push_json(200, json!({"data": {"Media": full["data"].take()}}));
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .with_entity_store(true)
+        .build()
+        .unwrap();
+    let store = client.entities().unwrap();
+
+    client
+        .execute::<Value>(SEARCH, json!({"id": 1}))
+        .await
+        .unwrap();
+    let anime = store.anime(1).unwrap();
+    assert!(!anime.is_full_loaded());
+    assert_eq!(anime.title.romaji.as_deref(), Some("Cowboy Bebop"));
+    assert_eq!(
+        store.populated_fields(EntityKind::Media, 1),
+        [
+            "bannerImage",
+            "characters",
+            "id",
+            "relations",
+            "title",
+            "type"
+        ]
+    );
+    assert!(store.is_populated(EntityKind::Media, 1, "title.romaji"));
+    assert!(store.is_populated(EntityKind::Media, 1, "title.english"));
+    assert!(store.is_populated(EntityKind::Media, 1, "bannerImage"));
+    assert!(!store.is_populated(EntityKind::Media, 1, "title.native"));
+    assert!(!store.is_populated(EntityKind::Media, 1, "episodes"));
+    assert!(!store.is_populated(EntityKind::Media, 1, "title.romaji.length"));
+
+    client.get_anime(1).await.unwrap();
+    let anime = store.anime(1).unwrap();
+    assert!(anime.is_full_loaded());
+    assert_eq!(anime.title.english.as_deref(), Some("Cowboy Bebop"));
+    assert_eq!(anime.episodes, Some(26));
+    assert_eq!(store.get(EntityKind::Media, 1).unwrap()["type"], "ANIME");
+    assert!(store.is_populated(EntityKind::Media, 1, "title.native"));
+    assert!(store
+        .populated_fields(EntityKind::Media, 1)
+        .contains(&"type".to_owned()));
+    assert!(store.manga(1).is_none());
+}
+
+#[test]
+fn nested_objects_are_merged_field_by_field() {
+    let store = EntityStore::new();
+    store.merge(&partial());
+    store.merge(&json!({"Media": {"id": 1, "title": {"english": "Cowboy Bebop"}}}));
+
+    assert_eq!(
+        store.get(EntityKind::Media, 1).unwrap()["title"],
+        json!({"romaji": "Cowboy Bebop", "english": "Cowboy Bebop"})
+    );
+    assert_eq!(
+        store.get(EntityKind::Media, 1).unwrap()["relations"]["edges"][0]["node"]["id"],
+        30
+    );
+}
+
+#[test]
+fn edges_are_not_mistaken_for_their_nodes() {
+    let store = EntityStore::new();
+    store.merge(&partial());
+
+    assert_eq!(store.len(), 3);
+    assert!(store.get(EntityKind::Character, 900).is_none());
+    assert_eq!(store.character(1).unwrap().name.full, "Spike Spiegel");
+    assert!(store.get(EntityKind::Media, 28).is_none());
+    assert!(store.anime(30).is_none());
+    assert_eq!(store.manga(30).unwrap().id, 30);
+    assert!(!store.manga(30).unwrap().is_full_loaded());
+
+    store.remove(EntityKind::Media, 30);
+    assert!(store.manga(30).is_none());
+    store.clear();
+    assert!(store.is_empty());
+}
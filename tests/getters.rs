@@ -0,0 +1,59 @@
+#![cfg(feature = "client")]
+
+use anilist::transport::MemoryTransport;
+use anilist::{AniListClientBuilder, Error, RetryPolicy};
+use serde_json::json;
+use std::sync::Arc;
+
+fn client(transport: &Arc<MemoryTransport>) -> anilist::AniListClient {
+    AniListClientBuilder::builder()
+        .with_transport(Arc::clone(transport))
+        .with_retry_policy(RetryPolicy::disabled())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn errors_on_nested_fields_keep_the_entity() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(
+        200,
+        json!({
+            "data": {"Media": {"id": 1, "relations": {"edges": [null]}}},
+            "errors": [{"message": "Not Found.", "status": 404, "path": ["Media", "relations", "edges", 0, "node"]}],
+        }),
+    );
+    transport.push_json(
+        200,
+        json!({
+            "data": {"Character": {"id": 2, "name": {"full": "Faye Valentine"}}},
+            "errors": [{"message": "Private.", "status": 403, "path": ["Character", "media"]}],
+        }),
+    );
+    let client = client(&transport);
+
+    let anime = client.get_anime(1).await.unwrap();
+    assert_eq!(anime.id, 1);
+    let character = client.get_character(2).await.unwrap();
+    assert_eq!(character.name.full, "Faye Valentine");
+}
+
+#[tokio::test]
+async fn errors_without_the_entity_fail() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(
+        200,
+        json!({
+            "data": {"Media": null},
+            "errors": [{"message": "Private.", "status": 403, "path": ["Media"]}],
+        }),
+    );
+    transport.push_json(200, json!({"data": {"Media": null}}));
+    let client = client(&transport);
+
+    match client.get_manga(1).await {
+        Err(Error::GraphQl(errors)) => assert_eq!(errors[0].status, Some(403)),
+        result => panic!("expected GraphQL errors, got {:?}", result),
+    }
+    assert!(matches!(client.get_manga(2).await, Err(Error::NotFound)));
+}
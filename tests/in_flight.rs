@@ -0,0 +1,103 @@
+#![cfg(feature = "client")]
+
+use anilist::transport::{MemoryTransport, Transport, TransportRequest, TransportResponse};
+use anilist::{AniListClientBuilder, Error};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+const MUTATION: &str = "mutation ($mediaId: Int) { SaveMediaListEntry (mediaId: $mediaId) { id } }";
+
+/// Answers after a second, so that requests overlap.
+struct SlowTransport(MemoryTransport);
+
+#[async_trait]
+impl Transport for SlowTransport {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        self.0.send(request).await
+    }
+}
+
+fn client(transport: &Arc<SlowTransport>, request_coalescing: bool) -> anilist::AniListClient {
+    AniListClientBuilder::builder()
+        .with_transport(Arc::clone(transport))
+        .with_request_coalescing(request_coalescing)
+        .build()
+        .unwrap()
+}
+
+fn transport(responses: &[Value]) -> Arc<SlowTransport> {
+    let transport = MemoryTransport::new();
+    for response in responses {
+        transport.push_json(200, response.clone());
+    }
+
+    Arc::new(SlowTransport(transport))
+}
+
+#[tokio::test(start_paused = true)]
+async fn identical_queries_share_one_request() {
+    let transport = transport(&[json!({"data": {"Media": {"id": 1}}})]);
+    let client = client(&transport, true);
+
+    let (first, second) = tokio::join!(client.get_anime(1), client.get_anime(1));
+    assert_eq!(first.unwrap().id, 1);
+    assert_eq!(second.unwrap().id, 1);
+    assert_eq!(transport.0.requests().len(), 1);
+
+    transport
+        .0
+        .push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    client.get_anime(1).await.unwrap();
+    assert_eq!(transport.0.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn different_queries_are_not_coalesced() {
+    let transport = transport(&[
+        json!({"data": {"Media": {"id": 1}}}),
+        json!({"data": {"Media": {"id": 2}}}),
+    ]);
+    let client = client(&transport, true);
+
+    let (first, second) = tokio::join!(client.get_anime(1), client.get_anime(2));
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(transport.0.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn mutations_and_disabled_coalescing_send_every_request() {
+    let transport = transport(&[
+        json!({"data": {"SaveMediaListEntry": {"id": 1}}}),
+        json!({"data": {"SaveMediaListEntry": {"id": 1}}}),
+    ]);
+    let client = client(&transport, true);
+    let (first, second) = tokio::join!(
+        client.execute::<Value>(MUTATION, json!({"mediaId": 1})),
+        client.execute::<Value>(MUTATION, json!({"mediaId": 1})),
+    );
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(transport.0.requests().len(), 2);
+
+    let transport = self::transport(&[
+        json!({"data": {"Media": {"id": 1}}}),
+        json!({"data": {"Media": {"id": 1}}}),
+    ]);
+    let client = self::client(&transport, false);
+    let (first, second) = tokio::join!(client.get_anime(1), client.get_anime(1));
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(transport.0.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_dropped_request_is_run_by_the_next_caller() {
+    let transport = transport(&[json!({"data": {"Media": {"id": 1}}})]);
+    let client = client(&transport, true);
+
+    let dropped = tokio::time::timeout(Duration::from_millis(500), client.get_anime(1)).await;
+    assert!(dropped.is_err());
+    let anime = client.get_anime(1).await.unwrap();
+    assert_eq!(anime.id, 1);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "client")]
+
+use anilist::metrics::{Histogram, InMemoryMetrics};
+use anilist::transport::{MemoryTransport, TransportResponse};
+use anilist::{AniListClientBuilder, CachePolicy, RetryPolicy};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test(start_paused = true)]
+async fn requests_are_counted_per_operation() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(TransportResponse::new(503, "Service Unavailable"));
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    transport.push_json(
+        404,
+        json!({"errors": [{"message": "Not Found.", "status": 404}], "data": {"Media": null}}),
+    );
+    transport.push_json(200, json!({"data": {"Character": {"id": 1}}}));
+    let metrics = Arc::new(InMemoryMetrics::new());
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .with_cache(CachePolicy::new())
+        .with_retry_policy(
+            RetryPolicy::new()
+                .with_backoff(Duration::from_secs(1), Duration::from_secs(30))
+                .with_jitter(false),
+        )
+        .with_metrics(Arc::clone(&metrics))
+        .build()
+        .unwrap();
+
+    client.get_anime(1).await.unwrap();
+    client.get_anime(1).await.unwrap();
+    client.get_anime(2).await.unwrap_err();
+    client.get_character(1).await.unwrap();
+
+    let media = metrics.operation("Media").unwrap();
+    assert_eq!(
+        (
+            media.requests,
+            media.errors,
+            media.cache_hits,
+            media.retries
+        ),
+        (3, 1, 1, 1)
+    );
+    assert_eq!(media.latency.count(), 3);
+    let character = metrics.operation("Character").unwrap();
+    assert_eq!(
+        (character.requests, character.errors, character.cache_hits),
+        (1, 0, 0)
+    );
+    assert_eq!(metrics.snapshot().len(), 2);
+
+    metrics.reset();
+    assert!(metrics.operation("Media").is_none());
+}
+
+#[test]
+fn latencies_fall_in_the_bucket_of_their_upper_bound() {
+    let mut histogram = Histogram::default();
+    assert_eq!(histogram.mean(), None);
+    for millis in [5, 10, 11, 600, 20_000] {
+        histogram.observe(Duration::from_millis(millis));
+    }
+
+    let buckets: Vec<(Option<Duration>, u64)> = histogram
+        .buckets()
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    assert_eq!(
+        buckets,
+        [
+            (Some(Duration::from_millis(10)), 2),
+            (Some(Duration::from_millis(25)), 1),
+            (Some(Duration::from_secs(1)), 1),
+            (None, 1),
+        ]
+    );
+    assert_eq!(histogram.buckets().count(), 11);
+    assert_eq!(histogram.count(), 5);
+    assert_eq!(histogram.sum(), Duration::from_millis(20_626));
+    assert_eq!(histogram.mean(), Some(Duration::from_micros(4_125_200)));
+}
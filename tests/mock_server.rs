@@ -0,0 +1,157 @@
+#![cfg(feature = "test-server")]
+
+use anilist::models::{Person, Status};
+use anilist::testing::MockServer;
+use anilist::{AniListClient, AniListClientBuilder, RetryPolicy};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await.unwrap();
+    server
+        .load_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+        .await
+        .unwrap();
+
+    server
+}
+
+fn client(server: &MockServer) -> AniListClient {
+    AniListClientBuilder::builder()
+        .with_initialized_reqwest_client()
+        .with_endpoint(server.url())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn the_client_is_answered_from_the_fixtures() {
+    let server = server().await;
+    let client = client(&server);
+
+    let anime = client.get_anime(1).await.unwrap();
+    assert_eq!(anime.title.romaji.as_deref(), Some("Cowboy Bebop"));
+    let manga = client.get_manga(30002).await.unwrap();
+    assert_eq!(manga.id, 30002);
+    let character = client.get_character(1).await.unwrap();
+    assert_eq!(character.name.full, "Spike Spiegel");
+    let query = AniListClient::get_query("person", "get").unwrap();
+    let data: serde_json::Value = client.execute(query, json!({"id": 95011})).await.unwrap();
+    let person: Person = serde_json::from_value(data["Staff"].clone()).unwrap();
+    assert_eq!(person.id, 95011);
+
+    let error = client.get_anime(2).await.unwrap_err();
+    assert_eq!(error.status(), Some(404));
+
+    let operations: Vec<_> = server
+        .requests()
+        .into_iter()
+        .map(|request| (request.operation.unwrap(), request.variables["id"].clone()))
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            ("Media".to_owned(), json!(1)),
+            ("Media".to_owned(), json!(30002)),
+            ("Character".to_owned(), json!(1)),
+            ("Staff".to_owned(), json!(95011)),
+            ("Media".to_owned(), json!(2)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn sessions_edit_list_entries_through_the_server() {
+    let server = server().await;
+    let session = client(&server).session("token");
+
+    let saved = session
+        .save_list_entry(1, Some(Status::Completed), Some(9.5))
+        .await
+        .unwrap();
+    let entry = &saved["data"]["SaveMediaListEntry"];
+    assert_eq!(entry["status"], "COMPLETED");
+    let deleted = session
+        .delete_list_entry(entry["id"].as_i64().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(deleted["data"]["DeleteMediaListEntry"]["deleted"], true);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0].variables,
+        json!({"mediaId": 1, "status": "COMPLETED", "score": 9.5})
+    );
+    for request in &requests {
+        assert_eq!(request.header("Authorization"), Some("Bearer token"));
+    }
+}
+
+#[tokio::test]
+async fn rate_limited_requests_are_retried_after_the_window() {
+    let server = server().await;
+    server.with_rate_limit(2, Duration::from_secs(2));
+    let client = client(&server);
+    client.get_anime(1).await.unwrap();
+    client.get_manga(30002).await.unwrap();
+    let rate_limit = client.rate_limit().unwrap();
+    assert_eq!((rate_limit.limit, rate_limit.remaining), (2, 0));
+
+    let response = reqwest::Client::new()
+        .post(server.url())
+        .json(&json!({"query": "query { Media { id } }"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 429);
+    let header = |name: &str| response.headers()[name].to_str().unwrap().to_owned();
+    assert_eq!(header("X-RateLimit-Limit"), "2");
+    assert_eq!(header("X-RateLimit-Remaining"), "0");
+    assert!(header("X-RateLimit-Reset").parse::<u64>().is_ok());
+    let retry_after: u64 = header("Retry-After").parse().unwrap();
+    assert!((1..=2).contains(&retry_after));
+
+    // A client that has not seen the rate limit yet is answered 429 and waits for Retry-After.
+    let started = Instant::now();
+    let character = self::client(&server).get_character(1).await.unwrap();
+    assert_eq!(character.id, 1);
+    assert!(started.elapsed() >= Duration::from_millis(500));
+    let operations: Vec<_> = server
+        .requests()
+        .into_iter()
+        .filter_map(|request| request.operation)
+        .collect();
+    assert_eq!(
+        operations,
+        ["Media", "Media", "Media", "Character", "Character"]
+    );
+}
+
+#[tokio::test]
+async fn injected_failures_are_retried_or_returned() {
+    let server = server().await;
+    let client = AniListClientBuilder::builder()
+        .with_initialized_reqwest_client()
+        .with_endpoint(server.url())
+        .with_retry_policy(
+            RetryPolicy::new().with_backoff(Duration::from_millis(10), Duration::from_millis(100)),
+        )
+        .build()
+        .unwrap();
+
+    server.fail_next(
+        503,
+        json!({"errors": [{"message": "Service Unavailable", "status": 503}]}),
+    );
+    assert_eq!(client.get_anime(1).await.unwrap().id, 1);
+    assert_eq!(server.requests().len(), 2);
+
+    server.fail_next(
+        400,
+        json!({"errors": [{"message": "Invalid token", "status": 400}]}),
+    );
+    let error = client.get_manga(30002).await.unwrap_err();
+    assert_eq!(error.status(), Some(400));
+    assert_eq!(server.requests().len(), 3);
+}
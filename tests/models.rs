@@ -0,0 +1,338 @@
+use anilist::models::{self, Anime, Character, Manga, ParseMode, Person, User};
+use anilist::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+const ANIME: &str = include_str!("fixtures/anime.json");
+const MANGA: &str = include_str!("fixtures/manga.json");
+const CHARACTER: &str = include_str!("fixtures/character.json");
+const STAFF: &str = include_str!("fixtures/staff.json");
+
+/// The data of a fixture, as AniList returns it for the root field of the query.
+fn fixture(fixture: &str) -> Value {
+    let mut fixture: Value = serde_json::from_str(fixture).unwrap();
+
+    fixture["data"].take()
+}
+
+fn parse<T: DeserializeOwned>(fixture: &str) -> T {
+    models::from_value(&self::fixture(fixture), ParseMode::Strict).unwrap()
+}
+
+fn decode_path<T: DeserializeOwned + std::fmt::Debug>(value: &Value) -> String {
+    match models::from_value::<T>(value, ParseMode::Strict) {
+        Err(Error::Decode { path, .. }) => path,
+        result => panic!("expected a decode error, got {:?}", result),
+    }
+}
+
+/// Checks that every value selected by the query is kept by the model, by comparing the fixture
+/// to the serialized model. `unmodeled` lists the selected fields the models have no field for,
+/// with their path without indices, e.g. `studios.nodes.media`.
+fn assert_selection_kept(fixture: &str, model: &impl Serialize, unmodeled: &[&str]) {
+    fn compare(selected: &Value, kept: &Value, path: &str, unmodeled: &[&str]) {
+        match (selected, kept) {
+            (Value::Object(selected), Value::Object(kept)) => {
+                for (key, selected) in selected {
+                    let path = match path {
+                        "" => key.clone(),
+                        path => format!("{}.{}", path, key),
+                    };
+                    if unmodeled.contains(&path.as_str()) {
+                        continue;
+                    }
+                    let kept = kept
+                        .get(key)
+                        .unwrap_or_else(|| panic!("{} is not kept by the model", path));
+                    compare(selected, kept, &path, unmodeled);
+                }
+            }
+            (Value::Array(selected), Value::Array(kept)) => {
+                assert_eq!(selected.len(), kept.len(), "{} has another length", path);
+                for (selected, kept) in selected.iter().zip(kept) {
+                    compare(selected, kept, path, unmodeled);
+                }
+            }
+            (selected, kept) => assert_eq!(selected, kept, "{} differs", path),
+        }
+    }
+
+    let kept = serde_json::to_value(model).unwrap();
+    compare(&self::fixture(fixture), &kept, "", unmodeled);
+}
+
+#[test]
+fn anime_keeps_every_selected_field() {
+    let anime: Anime = parse(ANIME);
+    assert_eq!(anime.id, 1);
+    assert_eq!(anime.format, models::Format::Tv);
+    assert_eq!(anime.season, Some(models::Season::Spring));
+    assert_eq!(anime.source, Some(models::Source::Original));
+    assert_eq!(anime.score.average, 86);
+    assert_eq!(
+        anime.cover.color,
+        Some(models::Color::Hex("#f1785d".to_owned()))
+    );
+    let relations = anime.relations.as_ref().unwrap();
+    assert_eq!(relations[0].media_type, models::MediaType::Manga);
+    assert_eq!(relations[0].manga.as_ref().unwrap().score.mean, 69);
+    assert_eq!(relations[1].relation_type, models::RelationType::SideStory);
+    assert_eq!(
+        relations[1].anime.as_ref().unwrap().format,
+        models::Format::Movie
+    );
+    let staff = &anime.staff.as_ref().unwrap()[0];
+    assert_eq!(staff.language, models::Language::Japanese);
+    assert_eq!(staff.gender, models::Gender::Male);
+    let link = &anime.external_links.as_ref().unwrap()[0];
+    assert_eq!(link.link_type, Some(models::LinkType::Streaming));
+
+    assert_selection_kept(
+        ANIME,
+        &anime,
+        &["studios.nodes.media", "nextAiringEpisode.mediaId"],
+    );
+}
+
+#[test]
+fn manga_keeps_every_selected_field() {
+    let manga: Manga = parse(MANGA);
+    assert_eq!(manga.id, 30002);
+    assert_eq!(manga.status, models::Status::Releasing);
+    assert_eq!(manga.chapters, None);
+    assert_eq!(manga.score.mean, 94);
+    let relation = &manga.relations.as_ref().unwrap()[0];
+    assert_eq!(relation.anime.as_ref().unwrap().id, 33);
+    let staff = &manga.staff.as_ref().unwrap()[0];
+    assert_eq!(
+        staff.primary_occupations,
+        Some(vec![models::occupations::Occupation::Mangaka])
+    );
+
+    assert_selection_kept(MANGA, &manga, &[]);
+}
+
+#[test]
+fn character_keeps_every_selected_field() {
+    let character: Character = parse(CHARACTER);
+    assert_eq!(character.id, 1);
+    assert_eq!(character.name.full, "Spike Spiegel");
+    assert_eq!(character.name.alternative_spoiler, ["Fearless"]);
+    assert_eq!(character.gender, Some(models::Gender::Male));
+    assert_eq!(character.age.as_deref(), Some("27"));
+
+    assert_selection_kept(CHARACTER, &character, &[]);
+}
+
+#[test]
+fn person_keeps_every_selected_field() {
+    let person: Person = parse(STAFF);
+    assert_eq!(person.id, 95011);
+    assert_eq!(person.years_active, Some((1985, 2024)));
+    assert_eq!(person.age, Some(63));
+    assert_eq!(
+        person.primary_occupations,
+        Some(vec![
+            models::occupations::Occupation::VoiceActor,
+            models::occupations::Occupation::Unknown("Theme Song Performance".to_owned()),
+        ])
+    );
+    assert_eq!(person.characters.as_ref().unwrap()[0].id, 1);
+
+    assert_selection_kept(STAFF, &person, &["staffMedia", "characterMedia"]);
+}
+
+#[test]
+fn fixtures_parse_the_same_in_both_modes() {
+    fn check<T: DeserializeOwned + PartialEq + std::fmt::Debug>(fixture: &str) {
+        let lenient: T = models::from_value(&self::fixture(fixture), ParseMode::Lenient).unwrap();
+        assert_eq!(lenient, parse::<T>(fixture));
+    }
+
+    check::<Anime>(ANIME);
+    check::<Manga>(MANGA);
+    check::<Character>(CHARACTER);
+    check::<Person>(STAFF);
+}
+
+#[test]
+fn strict_parsing_fails_with_the_path_of_the_invalid_value() {
+    let mut anime = fixture(ANIME);
+    anime["averageScore"] = json!("x");
+    assert_eq!(decode_path::<Anime>(&anime), "averageScore");
+
+    let mut anime = fixture(ANIME);
+    anime["characters"]["nodes"][0]["name"]["alternative"][0] = json!(5);
+    assert_eq!(
+        decode_path::<Anime>(&anime),
+        "characters.nodes[0].name.alternative[0]"
+    );
+
+    let mut manga = fixture(MANGA);
+    manga["meanScore"] = json!("x");
+    assert_eq!(decode_path::<Manga>(&manga), "meanScore");
+
+    let mut manga = fixture(MANGA);
+    manga["tags"][0]["rank"] = json!("high");
+    assert_eq!(decode_path::<Manga>(&manga), "tags[0].rank");
+
+    let mut character = fixture(CHARACTER);
+    character["dateOfBirth"]["day"] = json!("26th");
+    assert_eq!(decode_path::<Character>(&character), "dateOfBirth.day");
+
+    let mut person = fixture(STAFF);
+    person["yearsActive"][0] = json!("1985");
+    assert_eq!(decode_path::<Person>(&person), "yearsActive[0]");
+}
+
+#[test]
+fn lenient_parsing_replaces_invalid_values_by_their_default() {
+    let mut data = fixture(ANIME);
+    data["averageScore"] = json!("x");
+    data["trending"] = json!("many");
+    data["characters"]["nodes"][0]["name"]["alternative"][0] = json!(5);
+    let anime: Anime = models::from_value(&data, ParseMode::Lenient).unwrap();
+
+    let mut expected: Anime = parse(ANIME);
+    expected.score.average = 0;
+    expected.trending = None;
+    expected.characters.as_mut().unwrap()[0]
+        .name
+        .alternative
+        .clear();
+    assert_eq!(anime, expected);
+
+    let mut data = fixture(STAFF);
+    data["yearsActive"][0] = json!("1985");
+    data["gender"] = json!(5);
+    let person: Person = models::from_value(&data, ParseMode::Lenient).unwrap();
+
+    let mut expected: Person = parse(STAFF);
+    expected.years_active = None;
+    expected.gender = models::Gender::default();
+    assert_eq!(person, expected);
+}
+
+#[test]
+fn lenient_parsing_only_drops_entities_from_their_list() {
+    let data = json!({"id": 1, "characters": {"nodes": [
+        {"id": "x", "name": {"full": "Faye Valentine"}},
+        {"id": 2, "name": {"full": "Spike Spiegel", "alternative": ["Swimming Bird", 5]}},
+    ]}});
+    let anime: Anime = models::from_value(&data, ParseMode::Lenient).unwrap();
+    let characters = anime.characters.unwrap();
+    assert_eq!(characters.len(), 1);
+    assert_eq!(characters[0].id, 2);
+    assert!(characters[0].name.alternative.is_empty());
+}
+
+#[test]
+fn scores_keep_their_path() {
+    let data = json!({"id": 1, "averageScore": "x"});
+    assert_eq!(decode_path::<Anime>(&data), "averageScore");
+    let anime: Anime = models::from_value(&data, ParseMode::Lenient).unwrap();
+    assert_eq!(anime.id, 1);
+    assert_eq!(anime.score.average, 0);
+
+    let data = json!({"id": 2, "meanScore": [], "chapters": 10});
+    assert_eq!(decode_path::<Manga>(&data), "meanScore");
+    let manga: Manga = models::from_value(&data, ParseMode::Lenient).unwrap();
+    assert_eq!((manga.score.mean, manga.chapters), (0, Some(10)));
+
+    let statistics = json!({"id": 3, "statistics": {"anime": {
+        "count": 2,
+        "meanScore": 7.5,
+        "formats": [{"count": 2, "meanScore": "x", "format": "TV"}],
+    }}});
+    assert_eq!(
+        decode_path::<User>(&statistics),
+        "statistics.anime.formats[0].meanScore"
+    );
+    assert!(models::from_value::<User>(&statistics, ParseMode::Lenient).is_ok());
+}
+
+#[test]
+fn relation_nodes_keep_their_path() {
+    let mut data = fixture(ANIME);
+    data["relations"]["edges"][0]["node"]["title"]["native"] = json!(5);
+    assert_eq!(
+        decode_path::<Anime>(&data),
+        "relations.edges[0].node.title.native"
+    );
+    let anime: Anime = models::from_value(&data, ParseMode::Lenient).unwrap();
+    let mut expected: Anime = parse(ANIME);
+    let relation = &mut expected.relations.as_mut().unwrap()[0];
+    relation.manga.as_mut().unwrap().title.native.clear();
+    assert_eq!(anime, expected);
+
+    let mut data = fixture(ANIME);
+    data["relations"]["edges"][0]["node"]["chapters"] = json!("many");
+    assert_eq!(
+        decode_path::<Anime>(&data),
+        "relations.edges[0].node.chapters"
+    );
+    data["relations"]["edges"][0]["node"]["chapters"] = json!(42);
+    let anime: Anime = models::from_value(&data, ParseMode::Strict).unwrap();
+    let manga = anime.relations.as_ref().unwrap()[0].manga.as_ref().unwrap();
+    assert_eq!((manga.id, manga.chapters), (30, Some(42)));
+}
+
+#[test]
+fn relation_nodes_of_unknown_types_are_kept() {
+    let mut data = fixture(ANIME);
+    data["relations"]["edges"][1]["node"]["type"] = json!("NOVEL");
+    let anime: Anime = models::from_value(&data, ParseMode::Strict).unwrap();
+    let relation = &anime.relations.as_ref().unwrap()[1];
+    assert_eq!(
+        relation.media_type,
+        models::MediaType::Unknown("NOVEL".to_owned())
+    );
+    assert_eq!((relation.id, relation.anime.as_ref().unwrap().id), (29, 5));
+    assert_eq!(
+        models::from_value::<Anime>(&data, ParseMode::Lenient).unwrap(),
+        anime
+    );
+
+    let relations = serde_json::to_value(&anime).unwrap()["relations"].take();
+    assert_eq!(relations["edges"][1]["node"]["type"], "NOVEL");
+}
+
+#[test]
+fn api_enums_round_trip_through_their_strings() {
+    fn check<T>(value: &str, known: T)
+    where
+        T: FromStr<Err = Infallible> + Display + Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        assert_eq!(value.parse::<T>().unwrap(), known);
+        assert_eq!(known.to_string(), value);
+        assert_eq!(serde_json::to_value(&known).unwrap(), json!(value));
+        assert_eq!(serde_json::from_value::<T>(json!(value)).unwrap(), known);
+
+        let unknown = "NOT_YET_KNOWN".parse::<T>().unwrap();
+        assert_ne!(unknown, known);
+        assert_eq!(unknown.to_string(), "NOT_YET_KNOWN");
+        assert_eq!(
+            serde_json::to_value(&unknown).unwrap(),
+            json!("NOT_YET_KNOWN")
+        );
+        assert_eq!(
+            serde_json::from_value::<T>(json!("NOT_YET_KNOWN")).unwrap(),
+            unknown
+        );
+    }
+
+    check("TV_SHORT", models::Format::TvShort);
+    check("LIGHT_NOVEL", models::Source::LightNovel);
+    check("SIDE_STORY", models::RelationType::SideStory);
+    check("Japanese", models::Language::Japanese);
+    check("STREAMING", models::LinkType::Streaming);
+    check("MANGA", models::MediaType::Manga);
+    assert_eq!(
+        "NOVEL".parse::<models::MediaType>().unwrap(),
+        models::MediaType::Unknown("NOVEL".to_owned())
+    );
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "client")]
+
+use anilist::oauth::{self, ClientCredentials};
+use anilist::token_store::StoredToken;
+use anilist::transport::MemoryTransport;
+use anilist::{AniListClientBuilder, Error};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn jwt(claims: Value) -> String {
+    format!(
+        "{}.{}.signature",
+        URL_SAFE_NO_PAD.encode(json!({"typ": "JWT", "alg": "RS256"}).to_string()),
+        URL_SAFE_NO_PAD.encode(claims.to_string())
+    )
+}
+
+fn decode_path(access_token: &str) -> String {
+    match oauth::decode_token(access_token) {
+        Err(Error::Decode { path, .. }) => path,
+        result => panic!("expected a decode error, got {:?}", result),
+    }
+}
+
+#[test]
+fn claims_are_read_from_the_token() {
+    let claims = oauth::decode_token(&jwt(json!({
+        "sub": "5",
+        "iat": 1700000000,
+        "exp": 1731536000.5,
+    })))
+    .unwrap();
+
+    assert_eq!(claims.user_id, 5);
+    assert_eq!(
+        claims.issued_at,
+        Some(UNIX_EPOCH + Duration::from_secs(1700000000))
+    );
+    assert_eq!(
+        claims.expires_at,
+        UNIX_EPOCH + Duration::from_millis(1731536000500)
+    );
+    assert!(claims.is_expired());
+}
+
+#[test]
+fn out_of_range_timestamps_are_rejected() {
+    let token = jwt(json!({"sub": 5, "exp": 1e300}));
+    assert_eq!(decode_path(&token), "access_token.exp");
+    assert!(StoredToken::from_access_token(token).is_err());
+
+    let token = jwt(json!({"sub": 5, "iat": 1e300, "exp": 1731536000}));
+    assert_eq!(decode_path(&token), "access_token.iat");
+
+    let token = jwt(json!({"sub": 5}));
+    assert_eq!(decode_path(&token), "access_token.exp");
+}
+
+#[test]
+fn huge_durations_do_not_overflow() {
+    let claims = oauth::decode_token(&jwt(json!({"sub": 5, "exp": 4102444800u64}))).unwrap();
+    assert!(!claims.expires_within(Duration::from_secs(60)));
+    assert!(claims.expires_within(Duration::MAX));
+
+    let token = StoredToken::new(
+        5,
+        "token",
+        Some(SystemTime::now() + Duration::from_secs(3600)),
+    );
+    assert!(!token.expires_within(Duration::from_secs(60)));
+    assert!(token.expires_within(Duration::MAX));
+    assert!(!StoredToken::new(5, "token", None).expires_within(Duration::MAX));
+}
+
+#[tokio::test]
+async fn token_exchanges_are_never_recorded_nor_replayed() {
+    let directory = std::env::temp_dir().join(format!(
+        "anilist-recording-{}-{}",
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    let credentials = ClientCredentials::new("1", "client secret", "https://localhost/");
+    let token = json!({
+        "access_token": "secret access token",
+        "token_type": "Bearer",
+        "expires_in": 31536000,
+        "refresh_token": "secret refresh token",
+    });
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, token.clone());
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .with_recording(&directory)
+        .build()
+        .unwrap();
+
+    let access_token = client
+        .exchange_code(&credentials, "secret code")
+        .await
+        .unwrap();
+    assert_eq!(access_token.access_token, "secret access token");
+    client.get_anime(1).await.unwrap();
+
+    let recordings: Vec<String> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+        .collect();
+    assert_eq!(recordings.len(), 1);
+    for secret in [
+        "secret access token",
+        "secret refresh token",
+        "client secret",
+        "secret code",
+    ] {
+        assert!(!recordings[0].contains(secret), "{} was recorded", secret);
+    }
+
+    transport.push_json(200, token);
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .with_replay(&directory)
+        .build()
+        .unwrap();
+    let access_token = client
+        .exchange_code(&credentials, "secret code")
+        .await
+        .unwrap();
+    assert_eq!(access_token.access_token, "secret access token");
+    assert_eq!(client.get_anime(1).await.unwrap().id, 1);
+    assert_eq!(transport.requests().len(), 3);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
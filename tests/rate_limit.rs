@@ -0,0 +1,136 @@
+#![cfg(feature = "client")]
+
+use anilist::transport::{MemoryTransport, TransportResponse};
+use anilist::{AniListClientBuilder, Priority};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+fn client(transport: &Arc<MemoryTransport>) -> anilist::AniListClient {
+    AniListClientBuilder::builder()
+        .with_transport(Arc::clone(transport))
+        .with_rate_limit_pacing(false)
+        .build()
+        .unwrap()
+}
+
+fn rate_limited(id: i64, limit: u32, remaining: u32) -> TransportResponse {
+    TransportResponse::json(200, &json!({"data": {"Media": {"id": id}}}))
+        .with_header("X-RateLimit-Limit", limit.to_string())
+        .with_header("X-RateLimit-Remaining", remaining.to_string())
+}
+
+/// The ids of the media requested, in the order their requests were sent.
+fn requested_ids(transport: &MemoryTransport) -> Vec<i64> {
+    transport
+        .requests()
+        .iter()
+        .map(|request| {
+            let body: Value = serde_json::from_str(&request.body).unwrap();
+            body["variables"]["id"].as_i64().unwrap()
+        })
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limited_without_headers_does_not_block_forever() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(
+        429,
+        json!({"errors": [{"message": "Too Many Requests.", "status": 429}]}),
+    );
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    transport.push_json(200, json!({"data": {"Media": {"id": 2}}}));
+    let client = client(&transport);
+
+    let anime = tokio::time::timeout(Duration::from_secs(3600), client.get_anime(1))
+        .await
+        .expect("the client is blocked by an unknown rate limit")
+        .unwrap();
+    assert_eq!(anime.id, 1);
+
+    let anime = tokio::time::timeout(Duration::from_secs(3600), client.get_anime(2))
+        .await
+        .expect("later requests are blocked by an unknown rate limit")
+        .unwrap();
+    assert_eq!(anime.id, 2);
+    assert_eq!(transport.requests().len(), 3);
+    assert!(client.rate_limit().is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn background_requests_leave_a_quarter_of_the_budget() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(rate_limited(1, 4, 1));
+    transport.push_json(200, json!({"data": {"Media": {"id": 2}}}));
+    transport.push_json(200, json!({"data": {"Media": {"id": 3}}}));
+    let client = client(&transport);
+    client.get_anime(1).await.unwrap();
+
+    let started = Instant::now();
+    let background = client.with_priority(Priority::Background);
+    let background = tokio::spawn(async move {
+        background.get_anime(2).await.unwrap();
+        started.elapsed()
+    });
+    tokio::task::yield_now().await;
+    client.get_anime(3).await.unwrap();
+    assert_eq!(started.elapsed(), Duration::ZERO);
+
+    assert_eq!(background.await.unwrap(), Duration::from_secs(60));
+    assert_eq!(requested_ids(&transport), [1, 3, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn interactive_requests_are_admitted_before_the_others() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(rate_limited(1, 4, 0));
+    let client = client(&transport);
+    client.get_anime(1).await.unwrap();
+
+    let normal = client.clone();
+    let normal = tokio::spawn(async move { normal.get_anime(2).await });
+    tokio::task::yield_now().await;
+    let interactive = client.with_priority(Priority::Interactive);
+    let interactive = tokio::spawn(async move { interactive.get_anime(3).await });
+    tokio::task::yield_now().await;
+    assert_eq!(transport.requests().len(), 1);
+
+    transport.push_json(200, json!({"data": {"Media": {"id": 3}}}));
+    transport.push_json(200, json!({"data": {"Media": {"id": 2}}}));
+    assert_eq!(interactive.await.unwrap().unwrap().id, 3);
+    assert_eq!(normal.await.unwrap().unwrap().id, 2);
+    assert_eq!(requested_ids(&transport), [1, 3, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn extreme_rate_limit_headers_do_not_panic() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(
+        rate_limited(1, 90, u32::MAX).with_header("X-RateLimit-Reset", u64::MAX.to_string()),
+    );
+    transport.push_response(rate_limited(2, 90, u32::MAX));
+    let client = client(&transport);
+
+    assert_eq!(client.get_anime(1).await.unwrap().id, 1);
+    assert_eq!(client.rate_limit().unwrap().remaining, u32::MAX);
+    assert!(client.rate_limit().unwrap().reset_in() > Duration::from_secs(3600));
+    assert_eq!(client.get_anime(2).await.unwrap().id, 2);
+
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(
+        TransportResponse::json(
+            429,
+            &json!({"errors": [{"message": "Too Many Requests.", "status": 429}]}),
+        )
+        .with_header("Retry-After", u64::MAX.to_string()),
+    );
+    transport.push_json(200, json!({"data": {"Media": {"id": 3}}}));
+    let client = self::client(&transport);
+
+    let started = Instant::now();
+    assert_eq!(client.get_anime(3).await.unwrap().id, 3);
+    assert!(started.elapsed() <= Duration::from_secs(24 * 60 * 60));
+    assert_eq!(transport.requests().len(), 2);
+}
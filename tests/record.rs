@@ -0,0 +1,117 @@
+#![cfg(feature = "client")]
+
+use anilist::transport::{
+    MemoryTransport, RecordingTransport, ReplayTransport, Transport, TransportRequest,
+};
+use anilist::{AniListClientBuilder, Error, RetryPolicy};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const QUERY: &str = "query ($id: Int, $page: Int) { Media (id: $id) { id episodes } }";
+
+fn directory(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "anilist-{}-{}-{}",
+        name,
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ))
+}
+
+fn request(body: &str) -> TransportRequest {
+    TransportRequest {
+        url: "https://graphql.anilist.co".to_owned(),
+        headers: vec![("Authorization".to_owned(), "Bearer token".to_owned())],
+        body: body.to_owned(),
+        timeout: Duration::from_secs(20),
+    }
+}
+
+#[tokio::test]
+async fn recorded_responses_are_replayed() {
+    let directory = directory("replay");
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, json!({"data": {"Media": {"id": 1, "episodes": 26}}}));
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .with_recording(&directory)
+        .build()
+        .unwrap();
+    let data: Value = client
+        .execute(QUERY, json!({"id": 1, "page": 2}))
+        .await
+        .unwrap();
+    assert_eq!(data["Media"]["episodes"], 26);
+
+    let recordings: Vec<String> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+        .collect();
+    assert_eq!(recordings.len(), 1);
+    assert!(!recordings[0].contains("token"));
+
+    let client = AniListClientBuilder::builder()
+        .with_replay(&directory)
+        .build()
+        .unwrap();
+    let reformatted =
+        "query ($id: Int,  $page: Int) {\n  Media (id: $id) {\n    id\n    episodes\n  }\n}";
+    let data: Value = client
+        .execute(reformatted, json!({"page": 2, "id": 1}))
+        .await
+        .unwrap();
+    assert_eq!(data["Media"]["episodes"], 26);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[tokio::test]
+async fn variable_order_does_not_change_the_recording() {
+    let directory = directory("order");
+    let transport = MemoryTransport::new();
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    let recording = RecordingTransport::new(transport, &directory);
+    recording
+        .send(request(
+            r#"{"query": "query { Media { id } }", "variables": {"id": 1, "page": 2}}"#,
+        ))
+        .await
+        .unwrap();
+
+    let replay = ReplayTransport::new(&directory);
+    let response = replay
+        .send(request(
+            r#"{"query": "query {\n  Media { id }\n}", "variables": {"page": 2, "id": 1}}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        serde_json::from_str::<Value>(&response.body).unwrap(),
+        json!({"data": {"Media": {"id": 1}}})
+    );
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[tokio::test]
+async fn missing_recordings_fail_without_a_network_call() {
+    let directory = directory("missing");
+    std::fs::create_dir_all(&directory).unwrap();
+    let client = AniListClientBuilder::builder()
+        .with_replay(&directory)
+        .with_retry_policy(RetryPolicy::disabled())
+        .build()
+        .unwrap();
+
+    match client.execute::<Value>(QUERY, json!({"id": 2})).await {
+        Err(Error::Transport(error)) => {
+            assert!(error.to_string().starts_with("No recording found"))
+        }
+        result => panic!("expected a missing recording, got {:?}", result),
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
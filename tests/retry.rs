@@ -0,0 +1,131 @@
+#![cfg(feature = "client")]
+
+use anilist::transport::{MemoryTransport, TransportResponse};
+use anilist::{AniListClientBuilder, Error, RetryPolicy};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const MUTATION: &str = "mutation ($mediaId: Int) { SaveMediaListEntry (mediaId: $mediaId) { id } }";
+
+fn client(transport: &Arc<MemoryTransport>, retry_policy: RetryPolicy) -> anilist::AniListClient {
+    AniListClientBuilder::builder()
+        .with_transport(Arc::clone(transport))
+        .with_retry_policy(retry_policy)
+        .with_anilist_token("token")
+        .build()
+        .unwrap()
+}
+
+fn backoff() -> RetryPolicy {
+    RetryPolicy::new()
+        .with_backoff(Duration::from_secs(1), Duration::from_secs(30))
+        .with_jitter(false)
+}
+
+fn unavailable() -> TransportResponse {
+    TransportResponse::new(503, "Service Unavailable")
+}
+
+#[tokio::test(start_paused = true)]
+async fn transient_errors_are_retried_with_exponential_backoff() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(unavailable());
+    transport.push_json(
+        502,
+        json!({"errors": [{"message": "Bad Gateway", "status": 502}]}),
+    );
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    let client = client(&transport, backoff());
+
+    let started = Instant::now();
+    let anime = client.get_anime(1).await.unwrap();
+    assert_eq!(anime.id, 1);
+    assert_eq!(started.elapsed(), Duration::from_secs(1 + 2));
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retries_stop_after_the_last_attempt() {
+    let transport = Arc::new(MemoryTransport::new());
+    for _ in 0..3 {
+        transport.push_response(TransportResponse::new(500, "Internal Server Error"));
+    }
+    let client = client(&transport, backoff().with_max_attempts(2));
+
+    let error = client.get_anime(1).await.unwrap_err();
+    assert_eq!(error.status(), Some(500));
+    assert_eq!(transport.requests().len(), 2);
+    assert_eq!(transport.pending_responses(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn client_errors_are_not_retried() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(
+        400,
+        json!({"errors": [{"message": "Bad Request", "status": 400}]}),
+    );
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    let client = client(&transport, backoff());
+
+    let error = client.get_anime(1).await.unwrap_err();
+    assert_eq!(error.status(), Some(400));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn mutations_are_only_retried_when_allowed() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(unavailable());
+    transport.push_json(200, json!({"data": {"SaveMediaListEntry": {"id": 1}}}));
+    let client = client(&transport, backoff());
+
+    let error = client
+        .execute::<Value>(MUTATION, json!({"mediaId": 1}))
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(503));
+    assert_eq!(transport.requests().len(), 1);
+
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(unavailable());
+    transport.push_json(200, json!({"data": {"SaveMediaListEntry": {"id": 1}}}));
+    let client = self::client(&transport, backoff().with_retry_mutations(true));
+
+    let data: Value = client
+        .execute(MUTATION, json!({"mediaId": 1}))
+        .await
+        .unwrap();
+    assert_eq!(data, json!({"SaveMediaListEntry": {"id": 1}}));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn circuit_breaker_opens_after_consecutive_unavailable_responses() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_response(unavailable());
+    transport.push_response(unavailable());
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    let client = client(
+        &transport,
+        RetryPolicy::disabled().with_circuit_breaker(2, Duration::from_secs(60)),
+    );
+
+    for _ in 0..2 {
+        assert_eq!(client.get_anime(1).await.unwrap_err().status(), Some(503));
+    }
+    match client.get_anime(1).await {
+        Err(Error::Unavailable { retry_after }) => {
+            assert_eq!(retry_after, Duration::from_secs(60))
+        }
+        result => panic!("the circuit breaker is closed: {:?}", result),
+    }
+    assert_eq!(transport.requests().len(), 2);
+
+    tokio::time::advance(Duration::from_secs(60)).await;
+    let anime = client.get_anime(1).await.unwrap();
+    assert_eq!(anime.id, 1);
+    assert_eq!(transport.requests().len(), 3);
+}
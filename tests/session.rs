@@ -0,0 +1,67 @@
+#![cfg(feature = "client")]
+
+use anilist::models::Status;
+use anilist::transport::MemoryTransport;
+use anilist::AniListClientBuilder;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+fn session(transport: &Arc<MemoryTransport>) -> anilist::Session {
+    AniListClientBuilder::builder()
+        .with_transport(Arc::clone(transport))
+        .build()
+        .unwrap()
+        .session("token")
+}
+
+fn variables(transport: &MemoryTransport) -> Vec<Value> {
+    transport
+        .requests()
+        .iter()
+        .map(|request| serde_json::from_str::<Value>(&request.body).unwrap()["variables"].take())
+        .collect()
+}
+
+#[tokio::test]
+async fn list_entries_are_saved_with_the_given_fields() {
+    let transport = Arc::new(MemoryTransport::new());
+    let entry = json!({"id": 7, "mediaId": 1, "status": "COMPLETED", "score": 9.5, "progress": 26});
+    transport.push_json(200, json!({"data": {"SaveMediaListEntry": entry}}));
+    transport.push_json(200, json!({"data": {"SaveMediaListEntry": entry}}));
+    let session = session(&transport);
+
+    let saved = session
+        .save_list_entry(1, Some(Status::Completed), Some(9.5))
+        .await
+        .unwrap();
+    assert_eq!(saved["data"]["SaveMediaListEntry"], entry);
+    session.save_list_entry(1, None, None).await.unwrap();
+
+    assert_eq!(
+        variables(&transport),
+        [
+            json!({"mediaId": 1, "status": "COMPLETED", "score": 9.5}),
+            json!({"mediaId": 1}),
+        ]
+    );
+    let request = &transport.requests()[0];
+    assert_eq!(request.header("Authorization"), Some("Bearer token"));
+    assert!(request.body.contains("SaveMediaListEntry"));
+}
+
+#[tokio::test]
+async fn list_entries_are_deleted_by_their_id() {
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(
+        200,
+        json!({"data": {"DeleteMediaListEntry": {"deleted": true}}}),
+    );
+    let session = session(&transport);
+
+    let deleted = session.delete_list_entry(7).await.unwrap();
+    assert_eq!(deleted["data"]["DeleteMediaListEntry"]["deleted"], true);
+    assert_eq!(variables(&transport), [json!({"id": 7})]);
+    assert!(transport.requests()[0]
+        .body
+        .contains("DeleteMediaListEntry"));
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "tracing")]
+
+use anilist::transport::MemoryTransport;
+use anilist::AniListClientBuilder;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Keeps the `variables` field of the spans it sees.
+#[derive(Default)]
+struct Variables {
+    values: Mutex<Vec<String>>,
+    next_id: AtomicU64,
+}
+
+impl Visit for &Variables {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "variables" {
+            self.values.lock().unwrap().push(format!("{:?}", value));
+        }
+    }
+}
+
+impl Subscriber for Variables {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        span.record(&mut &*self);
+
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[tokio::test]
+async fn secret_variables_are_redacted_from_spans() {
+    let subscriber = Arc::new(Variables::default());
+    let _guard = tracing::subscriber::set_default(Arc::clone(&subscriber));
+    let transport = Arc::new(MemoryTransport::new());
+    transport.push_json(200, json!({"data": {"Media": {"id": 1}}}));
+    let client = AniListClientBuilder::builder()
+        .with_transport(Arc::clone(&transport))
+        .build()
+        .unwrap();
+
+    client
+        .execute::<Value>(
+            "query ($id: Int) { Media (id: $id) { id } }",
+            json!({
+                "id": 1,
+                "accessToken": "secret token",
+                "input": {"clientSecret": "secret client", "code": "secret code"},
+                "codes": ["kept"],
+            }),
+        )
+        .await
+        .unwrap();
+
+    let values = subscriber.values.lock().unwrap();
+    assert_eq!(values.len(), 1);
+    let variables: Value = serde_json::from_str(&values[0]).unwrap();
+    assert_eq!(
+        variables,
+        json!({
+            "id": 1,
+            "accessToken": "[redacted]",
+            "input": {"clientSecret": "[redacted]", "code": "[redacted]"},
+            "codes": ["kept"],
+        })
+    );
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "client")]
+
+use anilist::token_store::{FileTokenStore, StoredToken, TokenStore};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "anilist-{}-{}-{}",
+        name,
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::remove_dir_all(&directory).ok();
+
+    directory
+}
+
+#[tokio::test]
+async fn tokens_are_saved_and_replaced() {
+    let directory = directory("tokens");
+    let store = FileTokenStore::new(&directory);
+    let expires_at = UNIX_EPOCH + Duration::from_secs(4102444800);
+
+    store
+        .save(&StoredToken::new(5, "first", Some(expires_at)))
+        .await
+        .unwrap();
+    store
+        .save(&StoredToken::new(5, "second", Some(expires_at)))
+        .await
+        .unwrap();
+    store
+        .save(&StoredToken::new(6, "other", None))
+        .await
+        .unwrap();
+
+    let token = store.load(5).await.unwrap().unwrap();
+    assert_eq!(token, StoredToken::new(5, "second", Some(expires_at)));
+    assert_eq!(store.list().await.unwrap().len(), 2);
+    let mut files: Vec<String> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, ["5.json", "6.json"]);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn tokens_are_only_readable_by_their_owner() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let directory = directory("permissions");
+    std::fs::create_dir_all(&directory).unwrap();
+    // A temporary file left by a crash, readable by everyone.
+    let temporary_path = directory.join("5.json.tmp");
+    std::fs::write(&temporary_path, "{}").unwrap();
+    std::fs::set_permissions(&temporary_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    let store = FileTokenStore::new(&directory);
+    store
+        .save(&StoredToken::new(5, "token", None))
+        .await
+        .unwrap();
+
+    let metadata = std::fs::metadata(directory.join("5.json")).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    assert!(!temporary_path.exists());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}